@@ -74,6 +74,12 @@ impl Bitboard {
         }
     }
 
+    pub fn pop_lsb(&mut self) -> Option<usize> {
+        let square = self.first_set_bit()?;
+        self.0 &= self.0 - 1;
+        Some(square)
+    }
+
     pub fn last_set_bit(&self) -> Option<usize> {
         if self.0 == 0 {
             None
@@ -85,6 +91,34 @@ impl Bitboard {
     pub fn is_empty(&self) -> bool {
         self.0 == 0
     }
+
+    pub fn more_than_one(&self) -> bool {
+        self.0 & self.0.wrapping_sub(1) != 0
+    }
+
+    pub fn exactly_one(&self) -> bool {
+        self.0 != 0 && !self.more_than_one()
+    }
+
+    pub fn north_fill(&self) -> Bitboard {
+        let mut fill = self.0;
+        fill |= fill << 8;
+        fill |= fill << 16;
+        fill |= fill << 32;
+        Bitboard(fill)
+    }
+
+    pub fn south_fill(&self) -> Bitboard {
+        let mut fill = self.0;
+        fill |= fill >> 8;
+        fill |= fill >> 16;
+        fill |= fill >> 32;
+        Bitboard(fill)
+    }
+
+    pub fn file_fill(&self) -> Bitboard {
+        self.north_fill() | self.south_fill()
+    }
 }
 
 impl BitAnd for Bitboard {
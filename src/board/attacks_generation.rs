@@ -1,6 +1,102 @@
 use crate::bitboard::Bitboard;
 use crate::board::{Board, Color, Piece};
 use crate::constants::*;
+use once_cell::sync::Lazy;
+
+const RAY_DIRECTIONS: [(i32, i32); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (-1, 1),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+];
+const ROOK_RAYS: [usize; 4] = [0, 2, 5, 7];
+const BISHOP_RAYS: [usize; 4] = [1, 3, 4, 6];
+
+pub struct AttackTables {
+    pub knight: [Bitboard; BOARD_SIZE],
+    pub king: [Bitboard; BOARD_SIZE],
+    pub pawn: [[Bitboard; BOARD_SIZE]; 2],
+    pub rays: [[Bitboard; BOARD_SIZE]; 8],
+}
+
+impl AttackTables {
+    pub fn new() -> Self {
+        let mut knight = [Bitboard::new(); BOARD_SIZE];
+        let mut king = [Bitboard::new(); BOARD_SIZE];
+        let mut pawn = [[Bitboard::new(); BOARD_SIZE]; 2];
+        let mut rays = [[Bitboard::new(); BOARD_SIZE]; 8];
+
+        for square in 0..BOARD_SIZE {
+            knight[square] = AttackTables::offsets(
+                square,
+                &[
+                    (1, 2),
+                    (2, 1),
+                    (2, -1),
+                    (1, -2),
+                    (-1, -2),
+                    (-2, -1),
+                    (-2, 1),
+                    (-1, 2),
+                ],
+            );
+            king[square] = AttackTables::offsets(square, &RAY_DIRECTIONS);
+            pawn[Color::White as usize][square] = AttackTables::offsets(square, &[(-1, 1), (1, 1)]);
+            pawn[Color::Black as usize][square] =
+                AttackTables::offsets(square, &[(-1, -1), (1, -1)]);
+
+            for (direction, &(file_step, rank_step)) in RAY_DIRECTIONS.iter().enumerate() {
+                let mut file = (square % BOARD_WIDTH) as i32 + file_step;
+                let mut rank = (square / BOARD_WIDTH) as i32 + rank_step;
+
+                while AttackTables::on_board(file, rank) {
+                    rays[direction][square].set_bit(rank as usize * BOARD_WIDTH + file as usize);
+                    file += file_step;
+                    rank += rank_step;
+                }
+            }
+        }
+
+        Self {
+            knight,
+            king,
+            pawn,
+            rays,
+        }
+    }
+
+    fn on_board(file: i32, rank: i32) -> bool {
+        (0..BOARD_WIDTH as i32).contains(&file) && (0..BOARD_WIDTH as i32).contains(&rank)
+    }
+
+    fn offsets(square: usize, offsets: &[(i32, i32)]) -> Bitboard {
+        let mut attacks = Bitboard::new();
+        let file = (square % BOARD_WIDTH) as i32;
+        let rank = (square / BOARD_WIDTH) as i32;
+
+        for (file_offset, rank_offset) in offsets {
+            if AttackTables::on_board(file + file_offset, rank + rank_offset) {
+                attacks.set_bit(
+                    (rank + rank_offset) as usize * BOARD_WIDTH + (file + file_offset) as usize,
+                );
+            }
+        }
+
+        attacks
+    }
+}
+
+impl Default for AttackTables {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub static ATTACK_TABLES: Lazy<Box<AttackTables>> = Lazy::new(|| Box::new(AttackTables::new()));
 
 impl Board {
     pub fn print_attacks(&self, attacks: &Bitboard) {
@@ -96,7 +192,7 @@ impl Board {
                 while Board::is_index_in_bounds(to) {
                     attacks.set_bit(to as usize);
 
-                    if to as usize % BOARD_WIDTH == 0 || to as usize % BOARD_WIDTH == 7 {
+                    if (to as usize).is_multiple_of(BOARD_WIDTH) || to as usize % BOARD_WIDTH == 7 {
                         break;
                     }
 
@@ -166,4 +262,149 @@ impl Board {
 
         self.attacks[self.turn as usize][piece as usize] = attacks;
     }
+
+    pub fn knight_attacks(square: usize) -> Bitboard {
+        ATTACK_TABLES.knight[square]
+    }
+
+    pub fn king_attacks(square: usize) -> Bitboard {
+        ATTACK_TABLES.king[square]
+    }
+
+    pub fn pawn_attacks(color: Color, square: usize) -> Bitboard {
+        ATTACK_TABLES.pawn[color as usize][square]
+    }
+
+    fn ray_attacks(square: usize, occupancy: Bitboard, directions: &[usize]) -> Bitboard {
+        let mut attacks = Bitboard::new();
+
+        for &direction in directions {
+            let ray = ATTACK_TABLES.rays[direction][square];
+            let blockers = ray & occupancy;
+
+            // the first four directions point towards higher square indices
+            let blocker = if direction < 4 {
+                blockers.first_set_bit()
+            } else {
+                blockers.last_set_bit()
+            };
+
+            attacks = attacks
+                | match blocker {
+                    Some(blocker) => ray ^ ATTACK_TABLES.rays[direction][blocker],
+                    None => ray,
+                };
+        }
+
+        attacks
+    }
+
+    pub fn bishop_attacks(square: usize, occupancy: Bitboard) -> Bitboard {
+        Board::ray_attacks(square, occupancy, &BISHOP_RAYS)
+    }
+
+    pub fn rook_attacks(square: usize, occupancy: Bitboard) -> Bitboard {
+        Board::ray_attacks(square, occupancy, &ROOK_RAYS)
+    }
+
+    pub fn queen_attacks(square: usize, occupancy: Bitboard) -> Bitboard {
+        Board::bishop_attacks(square, occupancy) | Board::rook_attacks(square, occupancy)
+    }
+
+    pub fn occupied(&self) -> Bitboard {
+        self.occupancy[Color::White as usize] | self.occupancy[Color::Black as usize]
+    }
+
+    pub fn piece_attacks(
+        piece: Piece,
+        color: Color,
+        square: usize,
+        occupancy: Bitboard,
+    ) -> Bitboard {
+        match piece {
+            Piece::Pawn => Board::pawn_attacks(color, square),
+            Piece::Knight => Board::knight_attacks(square),
+            Piece::Bishop => Board::bishop_attacks(square, occupancy),
+            Piece::Rook => Board::rook_attacks(square, occupancy),
+            Piece::Queen => Board::queen_attacks(square, occupancy),
+            Piece::King => Board::king_attacks(square),
+        }
+    }
+
+    pub fn attackers_to(&self, square: usize, occupancy: Bitboard) -> Bitboard {
+        Board::attackers_to_with(&self.pieces, square, occupancy)
+    }
+
+    pub fn attackers_to_with(
+        pieces: &[[Bitboard; 6]; 2],
+        square: usize,
+        occupancy: Bitboard,
+    ) -> Bitboard {
+        let white = &pieces[Color::White as usize];
+        let black = &pieces[Color::Black as usize];
+        let bishops_queens = white[Piece::Bishop as usize]
+            | white[Piece::Queen as usize]
+            | black[Piece::Bishop as usize]
+            | black[Piece::Queen as usize];
+        let rooks_queens = white[Piece::Rook as usize]
+            | white[Piece::Queen as usize]
+            | black[Piece::Rook as usize]
+            | black[Piece::Queen as usize];
+
+        (Board::pawn_attacks(Color::Black, square) & white[Piece::Pawn as usize])
+            | (Board::pawn_attacks(Color::White, square) & black[Piece::Pawn as usize])
+            | (Board::knight_attacks(square)
+                & (white[Piece::Knight as usize] | black[Piece::Knight as usize]))
+            | (Board::king_attacks(square)
+                & (white[Piece::King as usize] | black[Piece::King as usize]))
+            | (Board::bishop_attacks(square, occupancy) & bishops_queens)
+            | (Board::rook_attacks(square, occupancy) & rooks_queens)
+    }
+
+    pub fn is_square_attacked(&self, square: usize, by: Color) -> bool {
+        !(self.attackers_to(square, self.occupied()) & self.occupancy[by as usize]).is_empty()
+    }
+
+    pub fn checkers(&self) -> Bitboard {
+        match self.king_square(self.turn) {
+            Some(king) => {
+                self.attackers_to(king, self.occupied())
+                    & self.occupancy[self.turn.opposite() as usize]
+            }
+            None => Bitboard::new(),
+        }
+    }
+
+    pub fn king_ring(&self, color: Color) -> Bitboard {
+        match self.king_square(color) {
+            Some(king) => Board::king_attacks(king) | Bitboard::from_index(king),
+            None => Bitboard::new(),
+        }
+    }
+
+    pub fn attackers_in_region(&self, region: Bitboard, color: Color) -> (u32, i32) {
+        let occupancy = self.occupied();
+        let mut count = 0;
+        let mut weight = 0;
+
+        for piece in [
+            Piece::Pawn,
+            Piece::Knight,
+            Piece::Bishop,
+            Piece::Rook,
+            Piece::Queen,
+            Piece::King,
+        ] {
+            let mut pieces = self.pieces[color as usize][piece as usize];
+
+            while let Some(square) = pieces.pop_lsb() {
+                if !(Board::piece_attacks(piece, color, square, occupancy) & region).is_empty() {
+                    count += 1;
+                    weight += KING_ATTACK_WEIGHTS[piece as usize];
+                }
+            }
+        }
+
+        (count, weight)
+    }
 }
@@ -0,0 +1,136 @@
+use crate::board::{Board, Color, Piece};
+use crate::constants::{ROW_1, ROW_8};
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FenError {
+    MissingFields(usize),
+    InvalidBoard(String),
+    InvalidPiece(char),
+    InvalidSideToMove(String),
+    InvalidCastlingRights(String),
+    InvalidEnPassant(String),
+    InvalidHalfmoveClock(String),
+    InvalidFullmoveNumber(String),
+    IllegalPosition(String),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MaterialLimits {
+    pub max_pawns: u32,
+    pub max_pieces: u32,
+}
+
+impl Default for MaterialLimits {
+    fn default() -> Self {
+        MaterialLimits {
+            max_pawns: 8,
+            max_pieces: 16,
+        }
+    }
+}
+
+impl Display for FenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FenError::MissingFields(count) => {
+                write!(f, "expected at least 4 fields, found {}", count)
+            }
+            FenError::InvalidBoard(board) => write!(f, "invalid piece placement '{}'", board),
+            FenError::InvalidPiece(c) => write!(f, "invalid piece '{}'", c),
+            FenError::InvalidSideToMove(side) => write!(f, "invalid side to move '{}'", side),
+            FenError::InvalidCastlingRights(rights) => {
+                write!(f, "invalid castling rights '{}'", rights)
+            }
+            FenError::InvalidEnPassant(square) => {
+                write!(f, "invalid en passant square '{}'", square)
+            }
+            FenError::InvalidHalfmoveClock(clock) => {
+                write!(f, "invalid halfmove clock '{}'", clock)
+            }
+            FenError::InvalidFullmoveNumber(number) => {
+                write!(f, "invalid fullmove number '{}'", number)
+            }
+            FenError::IllegalPosition(reason) => write!(f, "illegal position: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+impl Board {
+    pub fn from_fen(fen: &str) -> Result<Board, FenError> {
+        let mut board = Board::new();
+        board.try_set_fen(fen)?;
+        Ok(board)
+    }
+
+    pub fn from_fen_validated(fen: &str) -> Result<Board, FenError> {
+        let board = Board::from_fen(fen)?;
+        board.validate_legal()?;
+        Ok(board)
+    }
+
+    pub fn validate_legal(&self) -> Result<(), FenError> {
+        self.validate_legal_with(&MaterialLimits::default())
+    }
+
+    pub fn validate_legal_with(&self, limits: &MaterialLimits) -> Result<(), FenError> {
+        for color in [Color::White, Color::Black] {
+            let pieces = &self.pieces[color as usize];
+
+            if !pieces[Piece::King as usize].exactly_one() {
+                return Err(FenError::IllegalPosition(format!(
+                    "{:?} must have exactly one king",
+                    color
+                )));
+            }
+
+            let pawns = pieces[Piece::Pawn as usize].count_bits();
+            if pawns > limits.max_pawns {
+                return Err(FenError::IllegalPosition(format!(
+                    "{:?} has {} pawns",
+                    color, pawns
+                )));
+            }
+
+            let total = self.occupancy[color as usize].count_bits();
+            if total > limits.max_pieces {
+                return Err(FenError::IllegalPosition(format!(
+                    "{:?} has {} pieces",
+                    color, total
+                )));
+            }
+
+            if !(pieces[Piece::Pawn as usize] & (ROW_1 | ROW_8)).is_empty() {
+                return Err(FenError::IllegalPosition(format!(
+                    "{:?} has a pawn on the first or last rank",
+                    color
+                )));
+            }
+        }
+
+        if self.is_in_check(self.turn.opposite()) {
+            return Err(FenError::IllegalPosition(
+                "side not to move is in check".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Board {
+    type Err = FenError;
+
+    fn from_str(fen: &str) -> Result<Self, Self::Err> {
+        Board::from_fen(fen)
+    }
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_fen())
+    }
+}
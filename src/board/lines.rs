@@ -0,0 +1,81 @@
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::constants::*;
+use once_cell::sync::Lazy;
+
+pub struct Lines {
+    pub between: [[Bitboard; BOARD_SIZE]; BOARD_SIZE],
+    pub through: [[Bitboard; BOARD_SIZE]; BOARD_SIZE],
+}
+
+impl Lines {
+    pub fn new() -> Self {
+        let mut between = [[Bitboard::new(); BOARD_SIZE]; BOARD_SIZE];
+        let mut through = [[Bitboard::new(); BOARD_SIZE]; BOARD_SIZE];
+
+        for from in 0..BOARD_SIZE {
+            for to in 0..BOARD_SIZE {
+                if let Some((file_step, rank_step)) = Lines::direction(from, to) {
+                    between[from][to] = Lines::ray(from, file_step, rank_step, Some(to));
+                    through[from][to] = Lines::ray(from, file_step, rank_step, None)
+                        | Lines::ray(from, -file_step, -rank_step, None)
+                        | Bitboard::from_index(from);
+                }
+            }
+        }
+
+        Self { between, through }
+    }
+
+    fn direction(from: usize, to: usize) -> Option<(i32, i32)> {
+        if from == to {
+            return None;
+        }
+
+        let file_delta = (to % BOARD_WIDTH) as i32 - (from % BOARD_WIDTH) as i32;
+        let rank_delta = (to / BOARD_WIDTH) as i32 - (from / BOARD_WIDTH) as i32;
+
+        if file_delta == 0 || rank_delta == 0 || file_delta.abs() == rank_delta.abs() {
+            Some((file_delta.signum(), rank_delta.signum()))
+        } else {
+            None
+        }
+    }
+
+    fn ray(from: usize, file_step: i32, rank_step: i32, stop: Option<usize>) -> Bitboard {
+        let mut ray = Bitboard::new();
+        let mut file = (from % BOARD_WIDTH) as i32 + file_step;
+        let mut rank = (from / BOARD_WIDTH) as i32 + rank_step;
+
+        while (0..BOARD_WIDTH as i32).contains(&file) && (0..BOARD_WIDTH as i32).contains(&rank) {
+            let index = rank as usize * BOARD_WIDTH + file as usize;
+            if Some(index) == stop {
+                break;
+            }
+
+            ray.set_bit(index);
+            file += file_step;
+            rank += rank_step;
+        }
+
+        ray
+    }
+}
+
+impl Default for Lines {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub static LINES: Lazy<Box<Lines>> = Lazy::new(|| Box::new(Lines::new()));
+
+impl Board {
+    pub fn line_between(from: usize, to: usize) -> Bitboard {
+        LINES.between[from][to]
+    }
+
+    pub fn line_through(from: usize, to: usize) -> Bitboard {
+        LINES.through[from][to]
+    }
+}
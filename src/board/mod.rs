@@ -1,23 +1,41 @@
 mod attacks_generation;
+mod fen;
+mod lines;
 mod move_generation;
+mod outcome;
+mod san;
+mod see;
+mod uci;
 mod utils;
 mod zobrist;
 
+pub use fen::{FenError, MaterialLimits};
+pub use outcome::{DrawReason, GameOutcome};
+pub use san::MoveError;
+pub use see::SEE_PIECE_VALUES;
+pub use uci::MoveParseError;
+
 use crate::bitboard::Bitboard;
-use crate::board::zobrist::ZOBRIST;
+use crate::board::zobrist::{Zobrist, ZOBRIST};
 use crate::constants::*;
 use std::fmt::Display;
 
+#[derive(Clone)]
 pub struct Board {
     pub all_occupancy: Bitboard,
     pub occupancy: [Bitboard; 2],
     pub attacks: [[Bitboard; 6]; 2],
     pub pieces: [[Bitboard; 6]; 2],
+    pub king_squares: [Option<usize>; 2],
+    pub material_counts: [[u8; 6]; 2],
 
     pub turn: Color,
     pub ply: u32,
     pub game_state: GameState,
 
+    pub chess960: bool,
+    pub castling_rooks: [usize; 4],
+
     pub moves: Vec<Move>,
     pub zobrist_history: Vec<u64>,
     pub fen_history: Vec<String>,
@@ -31,6 +49,7 @@ pub struct GameState {
     pub castling_rights: u8,
     pub fifty_move_ply_count: u8,
     pub current_zobrist: u64,
+    pub last_irreversible_ply: u32,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -58,18 +77,46 @@ pub enum Piece {
     King = 5,
 }
 
+impl Piece {
+    // in value order, matching the index of each piece's bitboard
+    pub fn all() -> [Piece; 6] {
+        [
+            Piece::Pawn,
+            Piece::Knight,
+            Piece::Bishop,
+            Piece::Rook,
+            Piece::Queen,
+            Piece::King,
+        ]
+    }
+
+    pub fn from_char(c: char) -> Option<Piece> {
+        match c.to_ascii_lowercase() {
+            'p' => Some(Piece::Pawn),
+            'n' => Some(Piece::Knight),
+            'b' => Some(Piece::Bishop),
+            'r' => Some(Piece::Rook),
+            'q' => Some(Piece::Queen),
+            'k' => Some(Piece::King),
+            _ => None,
+        }
+    }
+
+    pub fn as_char(&self) -> char {
+        match self {
+            Piece::Pawn => 'p',
+            Piece::Knight => 'n',
+            Piece::Bishop => 'b',
+            Piece::Rook => 'r',
+            Piece::Queen => 'q',
+            Piece::King => 'k',
+        }
+    }
+}
+
 impl Display for Piece {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let str = match self {
-            Piece::Pawn => "p",
-            Piece::Knight => "n",
-            Piece::Bishop => "b",
-            Piece::Rook => "r",
-            Piece::Queen => "q",
-            Piece::King => "k",
-        }
-        .to_string();
-        write!(f, "{}", str)
+        write!(f, "{}", self.as_char())
     }
 }
 
@@ -98,6 +145,8 @@ impl Board {
             occupancy: [Bitboard::new(); 2],
             attacks: [[Bitboard::new(); 6]; 2],
             pieces: [[Bitboard::new(); 6]; 2],
+            king_squares: [None; 2],
+            material_counts: [[0; 6]; 2],
             turn: Color::White,
             game_state: GameState {
                 captured_piece: None,
@@ -105,8 +154,11 @@ impl Board {
                 castling_rights: CASTLING_RIGHTS[0] | CASTLING_RIGHTS[1],
                 fifty_move_ply_count: 0,
                 current_zobrist: 0,
+                last_irreversible_ply: 0,
             },
             ply: 1,
+            chess960: false,
+            castling_rooks: CASTLING_ROOKS,
             moves: Vec::new(),
             zobrist_history: Vec::new(),
             fen_history: Vec::new(),
@@ -116,6 +168,7 @@ impl Board {
                 castling_rights: CASTLING_RIGHTS[0] | CASTLING_RIGHTS[1],
                 fifty_move_ply_count: 0,
                 current_zobrist: 0,
+                last_irreversible_ply: 0,
             }],
         }
     }
@@ -127,9 +180,12 @@ impl Board {
     }
 
     pub fn reset(&mut self) {
+        self.all_occupancy = Bitboard::new();
         self.occupancy = [Bitboard::new(); 2];
         self.attacks = [[Bitboard::new(); 6]; 2];
         self.pieces = [[Bitboard::new(); 6]; 2];
+        self.king_squares = [None; 2];
+        self.material_counts = [[0; 6]; 2];
         self.turn = Color::White;
         self.game_state = GameState {
             captured_piece: None,
@@ -137,8 +193,11 @@ impl Board {
             castling_rights: CASTLING_RIGHTS[0] | CASTLING_RIGHTS[1],
             fifty_move_ply_count: 0,
             current_zobrist: 0,
+            last_irreversible_ply: 0,
         };
         self.ply = 0;
+        self.chess960 = false;
+        self.castling_rooks = CASTLING_ROOKS;
         self.moves = Vec::new();
         self.zobrist_history = Vec::new();
         self.fen_history = Vec::new();
@@ -146,74 +205,131 @@ impl Board {
     }
 
     pub fn set_fen(&mut self, fen: &str) {
+        if let Err(error) = self.try_set_fen(fen) {
+            panic!("Invalid FEN: {}", error);
+        }
+    }
+
+    pub fn try_set_fen(&mut self, fen: &str) -> Result<(), FenError> {
         self.reset();
-        let parts: Vec<&str> = fen.split_whitespace().collect();
-        let mut row = 7;
-        let mut col = 0;
-
-        for c in parts[0].chars() {
-            match c {
-                '/' => {
-                    row -= 1;
-                    col = 0;
-                }
-                '1'..='8' => {
-                    let offset = c.to_digit(10).unwrap() as usize;
-                    col += offset;
-                }
-                _ => {
-                    let color = if c.is_uppercase() {
-                        Color::White
-                    } else {
-                        Color::Black
-                    };
+        let mut parts: Vec<&str> = fen.split_whitespace().collect();
+        if parts.len() < 4 {
+            return Err(FenError::MissingFields(parts.len()));
+        }
+        // EPD-style positions leave out the clocks
+        if parts.len() == 4 {
+            parts.push("0");
+        }
+        if parts.len() == 5 {
+            parts.push("1");
+        }
 
-                    let piece = match c.to_ascii_lowercase() {
-                        'p' => Piece::Pawn,
-                        'n' => Piece::Knight,
-                        'b' => Piece::Bishop,
-                        'r' => Piece::Rook,
-                        'q' => Piece::Queen,
-                        'k' => Piece::King,
-                        _ => panic!("Invalid FEN"),
-                    };
+        let rows: Vec<&str> = parts[0].split('/').collect();
+        if rows.len() != BOARD_WIDTH {
+            return Err(FenError::InvalidBoard(parts[0].to_string()));
+        }
+
+        for (row_offset, row_str) in rows.iter().enumerate() {
+            let row = BOARD_WIDTH - 1 - row_offset;
+            let mut col = 0;
+
+            for c in row_str.chars() {
+                match c {
+                    '1'..='8' => {
+                        let offset = c.to_digit(10).unwrap() as usize;
+                        col += offset;
+                    }
+                    _ => {
+                        let color = if c.is_uppercase() {
+                            Color::White
+                        } else {
+                            Color::Black
+                        };
+
+                        let piece = Piece::from_char(c).ok_or(FenError::InvalidPiece(c))?;
+
+                        if col >= BOARD_WIDTH {
+                            return Err(FenError::InvalidBoard(parts[0].to_string()));
+                        }
 
-                    self.add_piece(color, piece, row * BOARD_WIDTH + col);
-                    col += 1;
+                        self.add_piece(color, piece, row * BOARD_WIDTH + col);
+                        col += 1;
+                    }
                 }
             }
+
+            if col != BOARD_WIDTH {
+                return Err(FenError::InvalidBoard(parts[0].to_string()));
+            }
         }
 
         self.turn = match parts[1] {
             "w" => Color::White,
             "b" => Color::Black,
-            _ => panic!("Invalid FEN"),
+            s => return Err(FenError::InvalidSideToMove(s.to_string())),
         };
 
         self.game_state.castling_rights = 0;
-        if parts[2].contains('K') {
-            self.game_state.castling_rights |= CASTLING_WHITE_KING;
-        }
-        if parts[2].contains('Q') {
-            self.game_state.castling_rights |= CASTLING_WHITE_QUEEN;
-        }
-        if parts[2].contains('k') {
-            self.game_state.castling_rights |= CASTLING_BLACK_KING;
-        }
-        if parts[2].contains('q') {
-            self.game_state.castling_rights |= CASTLING_BLACK_QUEEN;
+        if parts[2] != "-" {
+            for c in parts[2].chars() {
+                let (color, back_rank) = if c.is_ascii_uppercase() {
+                    (Color::White, ROW_1)
+                } else {
+                    (Color::Black, ROW_8)
+                };
+                let rank_offset = match color {
+                    Color::White => 0,
+                    Color::Black => 56,
+                };
+                let king_file = (self.pieces[color as usize][Piece::King as usize] & back_rank)
+                    .first_set_bit()
+                    .map_or(4, |square| square % 8);
+                let rooks = self.pieces[color as usize][Piece::Rook as usize] & back_rank;
+
+                let rook_file = match c.to_ascii_lowercase() {
+                    'k' => (king_file + 1..BOARD_WIDTH)
+                        .rev()
+                        .find(|&file| rooks.is_set(rank_offset + file))
+                        .unwrap_or(7),
+                    'q' => (0..king_file)
+                        .find(|&file| rooks.is_set(rank_offset + file))
+                        .unwrap_or(0),
+                    file @ 'a'..='h' => {
+                        self.chess960 = true;
+                        file as usize - 'a' as usize
+                    }
+                    _ => return Err(FenError::InvalidCastlingRights(parts[2].to_string())),
+                };
+
+                let index = color as usize * 2 + if rook_file > king_file { 0 } else { 1 };
+                self.game_state.castling_rights |= 1 << index;
+                self.castling_rooks[index] = rank_offset + rook_file;
+            }
         }
 
         self.game_state.en_passant_square = match parts[3] {
             "-" => None,
-            s => Some(Board::square_to_index(s)),
+            s => Some(Board::parse_square(s).ok_or(FenError::InvalidEnPassant(s.to_string()))?),
         };
 
-        self.game_state.fifty_move_ply_count = parts[4].parse().unwrap();
-        self.ply = (parts[5].parse::<u32>().unwrap() - 1) * 2
-            + if self.turn == Color::Black { 1 } else { 0 };
+        self.game_state.fifty_move_ply_count = parts[4]
+            .parse()
+            .map_err(|_| FenError::InvalidHalfmoveClock(parts[4].to_string()))?;
+        let fullmove = parts[5]
+            .parse::<u32>()
+            .ok()
+            .filter(|&fullmove| fullmove > 0)
+            .ok_or(FenError::InvalidFullmoveNumber(parts[5].to_string()))?;
+        self.ply = (fullmove - 1) * 2 + if self.turn == Color::Black { 1 } else { 0 };
+
+        // nothing before the loaded position can repeat
+        self.game_state.last_irreversible_ply = self.ply;
+        self.game_state.current_zobrist = ZOBRIST.hash(self);
+        self.game_state_history = vec![self.game_state];
+        self.zobrist_history = vec![self.game_state.current_zobrist];
+        self.fen_history = vec![self.to_fen()];
 
-        self.game_state.current_zobrist = ZOBRIST.hash(&self);
+        Ok(())
     }
 
     pub fn to_fen(&self) -> String {
@@ -234,14 +350,8 @@ impl Board {
 
                     let piece = self.piece_at(index).unwrap();
                     let c = match piece.color {
-                        Color::White => piece
-                            .piece
-                            .to_string()
-                            .to_uppercase()
-                            .chars()
-                            .next()
-                            .unwrap(),
-                        Color::Black => piece.piece.to_string().chars().next().unwrap(),
+                        Color::White => piece.piece.as_char().to_ascii_uppercase(),
+                        Color::Black => piece.piece.as_char(),
                     };
                     fen.push(c);
                 }
@@ -266,17 +376,21 @@ impl Board {
         if self.game_state.castling_rights == 0 {
             fen.push('-');
         } else {
-            if self.game_state.castling_rights & CASTLING_WHITE_KING != 0 {
-                fen.push('K');
-            }
-            if self.game_state.castling_rights & CASTLING_WHITE_QUEEN != 0 {
-                fen.push('Q');
-            }
-            if self.game_state.castling_rights & CASTLING_BLACK_KING != 0 {
-                fen.push('k');
-            }
-            if self.game_state.castling_rights & CASTLING_BLACK_QUEEN != 0 {
-                fen.push('q');
+            for (index, standard) in ['K', 'Q', 'k', 'q'].into_iter().enumerate() {
+                if self.game_state.castling_rights & (1 << index) == 0 {
+                    continue;
+                }
+
+                if self.chess960 {
+                    let file = (b'a' + (self.castling_rooks[index] % 8) as u8) as char;
+                    fen.push(if index < 2 {
+                        file.to_ascii_uppercase()
+                    } else {
+                        file
+                    });
+                } else {
+                    fen.push(standard);
+                }
             }
         }
 
@@ -298,17 +412,55 @@ impl Board {
     pub fn add_piece(&mut self, color: Color, piece: Piece, index: usize) {
         let bb = Bitboard::from_index(index);
 
+        self.all_occupancy = self.all_occupancy.or(&bb);
         self.occupancy[color as usize] = self.occupancy[color as usize].or(&bb);
         self.pieces[color as usize][piece as usize] =
             self.pieces[color as usize][piece as usize].or(&bb);
+
+        self.material_counts[color as usize][piece as usize] += 1;
+        if piece == Piece::King {
+            self.king_squares[color as usize] = Some(index);
+        }
     }
 
     pub fn remove_piece(&mut self, color: Color, piece: Piece, index: usize) {
         let bb = Bitboard::from_index(index);
 
+        self.all_occupancy = self.all_occupancy.and(&bb.not());
         self.occupancy[color as usize] = self.occupancy[color as usize].and(&bb.not());
         self.pieces[color as usize][piece as usize] =
             self.pieces[color as usize][piece as usize].and(&bb.not());
+
+        self.material_counts[color as usize][piece as usize] -= 1;
+        if piece == Piece::King && self.king_squares[color as usize] == Some(index) {
+            self.king_squares[color as usize] = None;
+        }
+    }
+
+    pub fn king_square(&self, color: Color) -> Option<usize> {
+        self.king_squares[color as usize]
+    }
+
+    pub fn material_counts(&self) -> [[u8; 6]; 2] {
+        self.material_counts
+    }
+
+    // 0 with only kings and pawns left, MAX_PHASE with every piece on the board
+    pub fn game_phase(&self) -> u8 {
+        let phase: i32 = self
+            .material_counts
+            .iter()
+            .flat_map(|counts| counts.iter().zip(PHASE_WEIGHTS))
+            .map(|(&count, weight)| count as i32 * weight)
+            .sum();
+
+        phase.min(MAX_PHASE) as u8
+    }
+
+    pub fn has_only_king(&self, color: Color) -> bool {
+        self.material_counts[color as usize][..Piece::King as usize]
+            .iter()
+            .all(|&count| count == 0)
     }
 
     pub fn move_piece(&mut self, color: Color, piece: Piece, from: usize, to: usize) {
@@ -316,6 +468,40 @@ impl Board {
         self.add_piece(color, piece, to);
     }
 
+    pub fn check_invariants(&self) -> bool {
+        let mut seen = Bitboard::new();
+
+        for color in [Color::White, Color::Black] {
+            let mut occupancy = Bitboard::new();
+            for &pieces in self.pieces[color as usize].iter() {
+                if !(seen & pieces).is_empty() {
+                    return false;
+                }
+                seen = seen | pieces;
+                occupancy = occupancy | pieces;
+            }
+
+            if occupancy != self.occupancy[color as usize] {
+                return false;
+            }
+
+            let counts_match = self.pieces[color as usize]
+                .iter()
+                .zip(self.material_counts[color as usize])
+                .all(|(pieces, count)| pieces.count_bits() == count as u32);
+            if !counts_match {
+                return false;
+            }
+
+            let kings = self.pieces[color as usize][Piece::King as usize];
+            if kings.exactly_one() && self.king_square(color) != kings.first_set_bit() {
+                return false;
+            }
+        }
+
+        seen == self.all_occupancy && self.game_state.current_zobrist == ZOBRIST.hash(self)
+    }
+
     pub fn print(&self) {
         println!();
         println!("  A B C D E F G H");
@@ -325,8 +511,8 @@ impl Board {
                 let index = row * BOARD_WIDTH + col;
 
                 let piece = match self.piece_at(index) {
-                    Some(p) => p.piece.to_string(),
-                    None => ".".to_string(),
+                    Some(p) => p.piece.as_char(),
+                    None => '.',
                 };
 
                 print!("{} ", piece);
@@ -337,132 +523,220 @@ impl Board {
     }
 
     pub fn is_empty_between(&self, from: usize, to: usize) -> bool {
-        let direction = (to as i32 - from as i32).signum();
-        let mut index = from as i32 + direction;
+        let occupancy =
+            self.occupancy[Color::White as usize] | self.occupancy[Color::Black as usize];
 
-        while index != to as i32 {
-            if self.occupancy[Color::White as usize].is_set(index as usize)
-                || self.occupancy[Color::Black as usize].is_set(index as usize)
-            {
-                return false;
-            }
-            index += direction;
-        }
+        (Board::line_between(from, to) & occupancy).is_empty()
+    }
 
-        true
+    fn castling_index(color: Color, is_king_side: bool) -> usize {
+        color as usize * 2 + if is_king_side { 0 } else { 1 }
     }
 
     pub fn can_castle(&self, color: Color, is_king_side: bool) -> bool {
-        let index = match color {
-            Color::White => 0,
-            Color::Black => 2,
-        } + if is_king_side { 0 } else { 1 };
-
+        let index = Board::castling_index(color, is_king_side);
         let mask = 1 << index;
 
         if self.game_state.castling_rights & mask == 0 {
             return false;
         }
 
-        let king_square = CASTLING_RIGHTS_SQUARES[index][0];
-        let rook_square = CASTLING_ROOKS[index];
-
-        self.is_empty_between(king_square, rook_square)
+        let king_square = self.pieces[color as usize][Piece::King as usize]
+            .first_set_bit()
+            .unwrap_or(CASTLING_RIGHTS_SQUARES[index][0]);
+        let rook_square = self.castling_rooks[index];
+        let king_target = CASTLING_RIGHTS_SQUARES[index][1];
+        let rook_target = CASTLING_ROOK_TARGETS[index];
+
+        // in Chess960 the king and rook may stand on each other's targets,
+        // so only pieces other than the castling pair can block
+        let path = Board::line_between(king_square, king_target)
+            | Board::line_between(rook_square, rook_target)
+            | Bitboard::from_index(king_target)
+            | Bitboard::from_index(rook_target);
+        let blockers = self.occupied()
+            & !Bitboard::from_index(king_square)
+            & !Bitboard::from_index(rook_square);
+
+        (path & blockers).is_empty()
     }
 
-    fn update_zobrist(&mut self, mv: &Move, square: usize) {
-        self.game_state.current_zobrist ^= ZOBRIST.pieces
-            [mv.piece as usize + if mv.color == Color::Black { 0 } else { 6 }][square];
+    pub fn is_in_check(&self, color: Color) -> bool {
+        match self.king_square(color) {
+            Some(king) => self.is_square_attacked(king, color.opposite()),
+            None => false,
+        }
     }
 
-    pub fn make_move(&mut self, mv: &Move) {
-        let mut new_zobrist = self.game_state.current_zobrist;
-        let mut new_castling_rights = self.game_state.castling_rights;
-        let mut new_en_passant_square = None;
+    pub fn can_castle_now(&self, color: Color, is_king_side: bool) -> bool {
+        if !self.can_castle(color, is_king_side) {
+            return false;
+        }
 
-        self.move_piece(mv.color, mv.piece, mv.from, mv.to);
+        let index = Board::castling_index(color, is_king_side);
+        let king_target = CASTLING_RIGHTS_SQUARES[index][1];
+        let back_rank = match color {
+            Color::White => ROW_1,
+            Color::Black => ROW_8,
+        };
 
-        // handle capture
-        if mv.capture.is_some() {
-            let mut capture_square = mv.to as i32;
+        let Some(king_square) =
+            (self.pieces[color as usize][Piece::King as usize] & back_rank).first_set_bit()
+        else {
+            return false;
+        };
+        if !self.pieces[color as usize][Piece::Rook as usize].is_set(self.castling_rooks[index]) {
+            return false;
+        }
 
-            // handle en passant capture
-            if mv.en_passant {
-                capture_square -= match mv.color {
-                    Color::White => MOVE_DOWN,
-                    Color::Black => MOVE_UP,
-                };
+        // the king may not start, pass through or land on an attacked square;
+        // in Chess960 the castling rook may shield one of those squares, so
+        // both castling pieces are lifted before looking for attackers
+        let transit = Board::line_between(king_square, king_target)
+            | Bitboard::from_index(king_square)
+            | Bitboard::from_index(king_target);
+        let mut occupancy = self.occupied();
+        occupancy.clear_bit(king_square);
+        occupancy.clear_bit(self.castling_rooks[index]);
+        let enemies = self.occupancy[color.opposite() as usize];
+        let mut squares = transit;
+        while let Some(square) = squares.pop_lsb() {
+            if !(self.attackers_to(square, occupancy) & enemies).is_empty() {
+                return false;
             }
+        }
+
+        true
+    }
+
+    pub fn repetition_count(&self) -> usize {
+        let current = self.game_state.current_zobrist;
+        let window = (self.ply - self.game_state.last_irreversible_ply) as usize;
+
+        self.zobrist_history
+            .iter()
+            .rev()
+            .take(window + 1)
+            .step_by(2)
+            .filter(|&&zobrist| zobrist == current)
+            .count()
+    }
+
+    pub fn last_irreversible_ply(&self) -> usize {
+        self.game_state.last_irreversible_ply as usize
+    }
 
-            self.remove_piece(
-                mv.color.opposite(),
-                mv.capture.unwrap(),
-                capture_square as usize,
-            );
+    pub fn is_threefold_repetition(&self) -> bool {
+        self.repetition_count() >= 3
+    }
+
+    pub fn castling_rook_squares(&self, king_to: usize) -> (usize, usize) {
+        let index = match king_to {
+            6 => 0,
+            2 => 1,
+            62 => 2,
+            58 => 3,
+            _ => panic!("Invalid castling move"),
+        };
+
+        (self.castling_rooks[index], CASTLING_ROOK_TARGETS[index])
+    }
+
+    fn zobrist_piece_key(color: Color, piece: Piece, square: usize) -> u64 {
+        ZOBRIST.pieces[Zobrist::piece_index(piece, color)][square]
+    }
 
-            self.update_zobrist(mv, capture_square as usize);
+    fn capture_square(mv: &Move) -> usize {
+        if mv.en_passant {
+            (mv.to as i32
+                - match mv.color {
+                    Color::White => MOVE_UP,
+                    Color::Black => MOVE_DOWN,
+                }) as usize
+        } else {
+            mv.to
         }
+    }
 
-        // handle castling
-        if mv.piece == Piece::King {
-            new_castling_rights &= !CASTLING_RIGHTS[mv.color as usize];
+    fn en_passant_after(mv: &Move) -> Option<usize> {
+        if mv.piece == Piece::Pawn && (mv.to as i32 - mv.from as i32).abs() == 16 {
+            Some((mv.from + mv.to) / 2)
+        } else {
+            None
+        }
+    }
 
-            if mv.castling {
-                let (rook_from, rook_to) = match mv.to {
-                    2 => (0, 3),
-                    6 => (7, 5),
-                    _ => panic!("Invalid castling move"),
-                };
+    fn castling_rights_after(&self, mv: &Move) -> u8 {
+        let mut castling_rights = self.game_state.castling_rights;
 
-                self.move_piece(mv.color, Piece::Rook, rook_from, rook_to);
-                self.update_zobrist(mv, rook_from);
-                self.update_zobrist(mv, rook_to);
+        if mv.piece == Piece::King {
+            castling_rights &= !CASTLING_RIGHTS[mv.color as usize];
+        }
+        for (index, &rook_square) in self.castling_rooks.iter().enumerate() {
+            if mv.from == rook_square || mv.to == rook_square {
+                castling_rights &= !(1 << index);
             }
         }
 
-        // handle promotion
-        if mv.promotion.is_some() {
-            self.remove_piece(mv.color, Piece::Pawn, mv.to);
-            self.add_piece(mv.color, mv.promotion.unwrap(), mv.to);
-            self.update_zobrist(mv, mv.to);
+        castling_rights
+    }
+
+    pub fn hash_after(&self, mv: &Move) -> u64 {
+        let mut zobrist = self.game_state.current_zobrist ^ ZOBRIST.side;
+
+        zobrist ^= Board::zobrist_piece_key(mv.color, mv.piece, mv.from);
+        zobrist ^= Board::zobrist_piece_key(mv.color, mv.promotion.unwrap_or(mv.piece), mv.to);
+
+        if let Some(captured) = mv.capture {
+            zobrist ^=
+                Board::zobrist_piece_key(mv.color.opposite(), captured, Board::capture_square(mv));
         }
 
-        // update en passant square
-        if mv.piece == Piece::Pawn && (mv.to as i32 - mv.from as i32).abs() == 16 {
-            let direction = match mv.color {
-                Color::White => MOVE_UP,
-                Color::Black => MOVE_DOWN,
-            };
-            new_en_passant_square = Some((mv.to as i32 - direction) as usize);
-            new_zobrist ^= ZOBRIST.en_passant[new_en_passant_square.unwrap() % 8];
+        if mv.castling {
+            let (rook_from, rook_to) = self.castling_rook_squares(mv.to);
+            zobrist ^= Board::zobrist_piece_key(mv.color, Piece::Rook, rook_from);
+            zobrist ^= Board::zobrist_piece_key(mv.color, Piece::Rook, rook_to);
         }
 
-        // update castling rights
-        if new_castling_rights != 0 {
-            if mv.from == CASTLING_ROOKS[0] || mv.to == CASTLING_ROOKS[0] {
-                new_castling_rights &= !CASTLING_WHITE_QUEEN;
-            }
-            if mv.from == CASTLING_ROOKS[1] || mv.to == CASTLING_ROOKS[1] {
-                new_castling_rights &= !CASTLING_WHITE_KING;
-            }
-            if mv.from == CASTLING_ROOKS[2] || mv.to == CASTLING_ROOKS[2] {
-                new_castling_rights &= !CASTLING_BLACK_QUEEN;
-            }
-            if mv.from == CASTLING_ROOKS[3] || mv.to == CASTLING_ROOKS[3] {
-                new_castling_rights &= !CASTLING_BLACK_KING;
-            }
+        if let Some(en_passant) = self.game_state.en_passant_square {
+            zobrist ^= ZOBRIST.en_passant[en_passant % 8];
+        }
+        if let Some(en_passant) = Board::en_passant_after(mv) {
+            zobrist ^= ZOBRIST.en_passant[en_passant % 8];
+        }
+
+        let castling_rights = self.castling_rights_after(mv);
+        if castling_rights != self.game_state.castling_rights {
+            zobrist ^= ZOBRIST.castling_rights[self.game_state.castling_rights as usize];
+            zobrist ^= ZOBRIST.castling_rights[castling_rights as usize];
         }
 
-        // update zobrist
-        let piece_index = mv.piece as usize + if mv.color == Color::Black { 0 } else { 6 };
-        new_zobrist ^= ZOBRIST.side;
-        new_zobrist ^= ZOBRIST.pieces[piece_index][mv.from];
-        new_zobrist ^= ZOBRIST.pieces[piece_index][mv.to];
-        new_zobrist ^= ZOBRIST.en_passant[self.game_state.en_passant_square.unwrap_or(0) % 8];
+        zobrist
+    }
+
+    pub fn make_move(&mut self, mv: &Move) {
+        let new_zobrist = self.hash_after(mv);
+        let new_castling_rights = self.castling_rights_after(mv);
 
-        if new_castling_rights != self.game_state.castling_rights {
-            new_zobrist ^= ZOBRIST.castling_rights[self.game_state.castling_rights as usize];
-            new_zobrist ^= ZOBRIST.castling_rights[new_castling_rights as usize];
+        if let Some(captured) = mv.capture {
+            self.remove_piece(mv.color.opposite(), captured, Board::capture_square(mv));
+        }
+
+        // the king and rook are lifted before either lands, as in Chess960
+        // they may swap squares
+        if mv.castling {
+            let (rook_from, rook_to) = self.castling_rook_squares(mv.to);
+            self.remove_piece(mv.color, Piece::King, mv.from);
+            self.remove_piece(mv.color, Piece::Rook, rook_from);
+            self.add_piece(mv.color, Piece::King, mv.to);
+            self.add_piece(mv.color, Piece::Rook, rook_to);
+        } else {
+            self.move_piece(mv.color, mv.piece, mv.from, mv.to);
+        }
+
+        if let Some(promotion) = mv.promotion {
+            self.remove_piece(mv.color, Piece::Pawn, mv.to);
+            self.add_piece(mv.color, promotion, mv.to);
         }
 
         // Update turn and move counters
@@ -473,13 +747,20 @@ impl Board {
         if mv.piece == Piece::Pawn || mv.capture.is_some() {
             new_fifty_move_ply_count = 0;
         }
+        let last_irreversible_ply =
+            if mv.piece == Piece::Pawn || mv.capture.is_some() || mv.castling {
+                self.ply
+            } else {
+                self.game_state.last_irreversible_ply
+            };
 
         let new_game_state = GameState {
             captured_piece: mv.capture,
-            en_passant_square: new_en_passant_square,
+            en_passant_square: Board::en_passant_after(mv),
             castling_rights: new_castling_rights,
             fifty_move_ply_count: new_fifty_move_ply_count,
             current_zobrist: new_zobrist,
+            last_irreversible_ply,
         };
 
         self.game_state = new_game_state;
@@ -487,6 +768,18 @@ impl Board {
         self.zobrist_history.push(new_zobrist);
         self.fen_history.push(self.to_fen());
         self.moves.push(*mv);
+
+        debug_assert!(self.check_invariants());
+    }
+
+    pub fn make_move_checked(&mut self, mv: &Move) -> Result<(), MoveError> {
+        // a Chess960 castle may land the king on its own rook
+        if !mv.castling && self.occupancy[mv.color as usize].is_set(mv.to) {
+            return Err(MoveError::OccupiedByFriendly(Board::index_to_square(mv.to)));
+        }
+
+        self.make_move(mv);
+        Ok(())
     }
 
     pub fn undo_move(&mut self, mv: &Move) {
@@ -497,46 +790,79 @@ impl Board {
             panic!("Invalid move");
         }
 
-        if mv.promotion == Some(Piece::Pawn) {
-            self.remove_piece(mv.color, mv.promotion.unwrap(), mv.to);
+        if let Some(promotion) = mv.promotion {
+            self.remove_piece(mv.color, promotion, mv.to);
             self.add_piece(mv.color, Piece::Pawn, mv.to);
         }
 
-        self.move_piece(mv.color, mv.piece, mv.to, mv.from);
-
-        if mv.capture.is_some() {
-            let mut capture_square = mv.to as i32;
+        if mv.castling {
+            let (rook_from, rook_to) = self.castling_rook_squares(mv.to);
 
-            if mv.en_passant {
-                capture_square -= match mv.color {
-                    Color::White => MOVE_DOWN,
-                    Color::Black => MOVE_UP,
-                };
-            }
+            self.remove_piece(mv.color, Piece::King, mv.to);
+            self.remove_piece(mv.color, Piece::Rook, rook_to);
+            self.add_piece(mv.color, Piece::King, mv.from);
+            self.add_piece(mv.color, Piece::Rook, rook_from);
+        } else {
+            self.move_piece(mv.color, mv.piece, mv.to, mv.from);
+        }
 
-            self.add_piece(
-                mv.color.opposite(),
-                mv.capture.unwrap(),
-                capture_square as usize,
-            );
+        if let Some(captured) = mv.capture {
+            self.add_piece(mv.color.opposite(), captured, Board::capture_square(mv));
         }
 
-        if mv.piece == Piece::King {
-            if mv.castling {
-                let (rook_from, rook_to) = match mv.to {
-                    2 => (0, 3),
-                    6 => (7, 5),
-                    _ => panic!("Invalid castling move"),
-                };
+        self.game_state_history.pop();
+        self.game_state = *self.game_state_history.last().unwrap();
+        self.zobrist_history.pop();
+        self.fen_history.pop();
+        self.ply -= 1;
 
-                self.move_piece(mv.color, Piece::Rook, rook_to, rook_from);
-            }
+        debug_assert!(self.check_invariants());
+    }
+
+    pub fn hash_after_null(&self) -> u64 {
+        let mut zobrist = self.game_state.current_zobrist ^ ZOBRIST.side;
+
+        if let Some(en_passant) = self.game_state.en_passant_square {
+            zobrist ^= ZOBRIST.en_passant[en_passant % 8];
         }
 
+        zobrist
+    }
+
+    // passes the turn without moving, the side to move must not be in check
+    pub fn make_null_move(&mut self) {
+        let new_zobrist = self.hash_after_null();
+
+        self.turn = self.turn.opposite();
+        self.ply += 1;
+
+        // positions before the null move cannot be repeated through it
+        let new_game_state = GameState {
+            captured_piece: None,
+            en_passant_square: None,
+            castling_rights: self.game_state.castling_rights,
+            fifty_move_ply_count: self.game_state.fifty_move_ply_count + 1,
+            current_zobrist: new_zobrist,
+            last_irreversible_ply: self.ply,
+        };
+
+        self.game_state = new_game_state;
+        self.game_state_history.push(new_game_state);
+        self.zobrist_history.push(new_zobrist);
+        self.fen_history.push(self.to_fen());
+
+        debug_assert!(self.check_invariants());
+    }
+
+    pub fn undo_null_move(&mut self) {
+        self.turn = self.turn.opposite();
+
         self.game_state_history.pop();
-        self.game_state = self.game_state_history.last().unwrap().clone();
+        self.game_state = *self.game_state_history.last().unwrap();
         self.zobrist_history.pop();
         self.fen_history.pop();
         self.ply -= 1;
+
+        debug_assert!(self.check_invariants());
     }
 }
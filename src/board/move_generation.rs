@@ -11,18 +11,13 @@ impl Board {
             return moves;
         }
 
-        moves.extend(&self.generate_pawn_moves());
-        moves.extend(&self.generate_bishop_moves());
-        moves.extend(&self.generate_knight_moves());
-        moves.extend(&self.generate_rook_moves());
-        moves.extend(&self.generate_queen_moves());
-        moves.extend(&self.generate_king_moves());
+        moves.extend(self.generate_pseudo_legal_moves());
 
         println!("Possible {:?} moves:", moves.len());
         moves.iter().for_each(|m: &Move| {
             let mut move_str = Board::index_to_square(m.from) + &Board::index_to_square(m.to);
             if let Some(promotion) = m.promotion {
-                move_str.push_str(&promotion.to_string());
+                move_str.push(promotion.as_char());
             }
             print!("{:?} ", move_str);
         });
@@ -30,158 +25,253 @@ impl Board {
         moves
     }
 
-    pub fn generate_pawn_moves(&self) -> Vec<Move> {
+    pub fn generate_pseudo_legal_moves(&self) -> Vec<Move> {
+        self.generate_pseudo_legal_moves_to(!Bitboard::new())
+    }
+
+    // only moves landing on a square in `target` are generated
+    fn generate_pseudo_legal_moves_to(&self, target: Bitboard) -> Vec<Move> {
+        if self.has_only_king(self.turn) {
+            return self.generate_king_moves_to(target);
+        }
+
         let mut moves = Vec::new();
-        let pawns = self.pieces[self.turn as usize][Piece::Pawn as usize];
 
-        // TODO: Validate check
+        moves.extend(&self.generate_pawn_moves_to(target));
+        moves.extend(&self.generate_piece_moves(Piece::Bishop, target));
+        moves.extend(&self.generate_piece_moves(Piece::Knight, target));
+        moves.extend(&self.generate_piece_moves(Piece::Rook, target));
+        moves.extend(&self.generate_piece_moves(Piece::Queen, target));
+        moves.extend(&self.generate_king_moves_to(target));
 
-        for i in 0..BOARD_SIZE {
-            if !pawns.is_set(i) {
-                continue;
-            }
+        moves
+    }
+
+    /// Every legal move for the side to move.
+    ///
+    /// ```
+    /// use aether::board::Board;
+    ///
+    /// let board = Board::init();
+    /// assert_eq!(board.legal_moves().len(), 20);
+    /// ```
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+        crate::movegen::legal(self, &mut moves);
+        moves
+    }
+
+    pub fn generate_legal_moves(&self) -> Vec<Move> {
+        self.generate_legal_moves_to(!Bitboard::new())
+    }
+
+    /// Every legal move landing on a square in `target`, with the mask
+    /// applied while generating rather than afterwards.
+    pub fn generate_legal_moves_to(&self, target: Bitboard) -> Vec<Move> {
+        // in double check only the king can move
+        let moves = if self.checkers().more_than_one() {
+            self.generate_king_moves_to(target)
+        } else {
+            self.generate_pseudo_legal_moves_to(target)
+        };
 
-            let direction = match self.turn {
-                Color::White => MOVE_UP,
-                Color::Black => MOVE_DOWN,
+        moves
+            .into_iter()
+            .filter(|mv| self.leaves_king_safe(mv))
+            .collect()
+    }
+
+    pub fn has_legal_move(&self) -> bool {
+        if self.checkers().more_than_one() {
+            return self
+                .generate_king_moves()
+                .iter()
+                .any(|mv| self.leaves_king_safe(mv));
+        }
+
+        // king moves first, they are the ones that run out in mating positions
+        [
+            Piece::King,
+            Piece::Knight,
+            Piece::Pawn,
+            Piece::Bishop,
+            Piece::Rook,
+            Piece::Queen,
+        ]
+        .into_iter()
+        .any(|piece| {
+            let moves = match piece {
+                Piece::Pawn => self.generate_pawn_moves(),
+                Piece::King => self.generate_king_moves(),
+                _ => self.generate_piece_moves(piece, !Bitboard::new()),
             };
+            moves.iter().any(|mv| self.leaves_king_safe(mv))
+        })
+    }
 
-            let from = i;
-            let possible_to = i as i32 + direction;
+    /// Whether `mv` is legal in this position, checked on its own without
+    /// generating the whole move list.
+    pub fn is_legal(&self, mv: &Move) -> bool {
+        let us = self.turn as usize;
+        let moving = self.piece_at(mv.from);
+        if mv.color != self.turn
+            || moving.map(|piece_at| (piece_at.piece, piece_at.color)) != Some((mv.piece, mv.color))
+        {
+            return false;
+        }
 
-            if !Board::is_index_in_bounds(possible_to) {
-                continue;
-            }
+        if mv.castling {
+            let is_king_side = match (mv.color, mv.to) {
+                (Color::White, 6) | (Color::Black, 62) => true,
+                (Color::White, 2) | (Color::Black, 58) => false,
+                _ => return false,
+            };
+            return mv.piece == Piece::King
+                && !mv.en_passant
+                && mv.capture.is_none()
+                && mv.promotion.is_none()
+                && self.can_castle_now(mv.color, is_king_side)
+                && self.leaves_king_safe(mv);
+        }
 
-            let to = possible_to as usize;
-            let left = (to as i32 + MOVE_LEFT) as usize;
-            let right = (to as i32 + MOVE_RIGHT) as usize;
+        if self.occupancy[us].is_set(mv.to) {
+            return false;
+        }
 
-            // DOUBLE PUSH
-            if (ROW_2.is_set(from) && self.turn == Color::White)
-                || (ROW_7.is_set(from) && self.turn == Color::Black)
+        if mv.en_passant {
+            if mv.piece != Piece::Pawn
+                || mv.capture != Some(Piece::Pawn)
+                || mv.promotion.is_some()
+                || self.game_state.en_passant_square != Some(mv.to)
+                || !Board::pawn_attacks(mv.color, mv.from).is_set(mv.to)
             {
-                let double = to as i32 + direction;
-                if self.is_square_empty(to) && self.is_square_empty(double as usize) {
-                    moves.push(Move {
-                        from,
-                        to: double as usize,
-                        piece: Piece::Pawn,
-                        color: self.turn,
-                        en_passant: false,
-                        castling: false,
-                        promotion: None,
-                        capture: None,
-                    });
-                }
+                return false;
             }
+            return self.leaves_king_safe(mv);
+        }
 
-            // EN PASSANT
-            if let Some(ep) = self.game_state.en_passant_square {
-                if left == ep {
-                    moves.push(Move {
-                        from,
-                        to: left,
-                        piece: Piece::Pawn,
-                        color: self.turn,
-                        en_passant: true,
-                        castling: false,
-                        promotion: None,
-                        capture: Some(Piece::Pawn),
-                    });
-                }
-                if right == ep {
-                    moves.push(Move {
-                        from,
-                        to: right,
-                        piece: Piece::Pawn,
-                        color: self.turn,
-                        en_passant: true,
-                        castling: false,
-                        promotion: None,
-                        capture: Some(Piece::Pawn),
-                    });
-                }
-            }
+        if mv.capture != self.piece_at(mv.to).map(|piece_at| piece_at.piece) {
+            return false;
+        }
 
-            // CAPTURES
-            if self.is_enemy(left) {
-                if let Some(piece_at) = self.piece_at(left) {
-                    moves.push(Move {
-                        from,
-                        to: left,
-                        piece: Piece::Pawn,
-                        color: self.turn,
-                        en_passant: false,
-                        castling: false,
-                        promotion: None,
-                        capture: Some(piece_at.piece),
-                    });
-                }
-            }
-            if self.is_enemy(right) {
-                if let Some(piece_at) = self.piece_at(right) {
-                    moves.push(Move {
-                        from,
-                        to: right,
-                        piece: Piece::Pawn,
-                        color: self.turn,
-                        en_passant: false,
-                        castling: false,
-                        promotion: None,
-                        capture: Some(piece_at.piece),
-                    });
+        let reachable = if mv.piece == Piece::Pawn {
+            let last_rank = ROW_8.is_set(mv.to) || ROW_1.is_set(mv.to);
+            let valid_promotion = match mv.promotion {
+                Some(Piece::Pawn) | Some(Piece::King) => false,
+                Some(_) => last_rank,
+                None => !last_rank,
+            };
+            let (direction, start_row) = match mv.color {
+                Color::White => (MOVE_UP, ROW_2),
+                Color::Black => (MOVE_DOWN, ROW_7),
+            };
+            let single = mv.from as i32 + direction;
+            let push = if mv.capture.is_some() {
+                Board::pawn_attacks(mv.color, mv.from).is_set(mv.to)
+            } else if mv.to as i32 == single {
+                true
+            } else {
+                start_row.is_set(mv.from)
+                    && mv.to as i32 == single + direction
+                    && self.is_square_empty(single as usize)
+            };
+            valid_promotion && push && (mv.capture.is_some() || self.is_square_empty(mv.to))
+        } else {
+            mv.promotion.is_none()
+                && Board::piece_attacks(mv.piece, mv.color, mv.from, self.occupied()).is_set(mv.to)
+        };
+
+        reachable && self.leaves_king_safe(mv)
+    }
+
+    pub fn legal_moves_with_hashes(&self) -> Vec<(Move, u64)> {
+        self.generate_legal_moves()
+            .into_iter()
+            .map(|mv| (mv, self.hash_after(&mv)))
+            .collect()
+    }
+
+    pub fn generate_legal_captures(&self) -> Vec<Move> {
+        self.generate_legal_moves()
+            .into_iter()
+            .filter(|mv| mv.capture.is_some())
+            .collect()
+    }
+
+    fn pieces_after(&self, mv: &Move) -> [[Bitboard; 6]; 2] {
+        let us = mv.color as usize;
+        let them = mv.color.opposite() as usize;
+        let mut pieces = self.pieces;
+
+        pieces[us][mv.piece as usize].clear_bit(mv.from);
+        pieces[us][mv.promotion.unwrap_or(mv.piece) as usize].set_bit(mv.to);
+
+        if let Some(captured) = mv.capture {
+            let capture_square = if mv.en_passant {
+                match mv.color {
+                    Color::White => mv.to - 8,
+                    Color::Black => mv.to + 8,
                 }
-            }
+            } else {
+                mv.to
+            };
+            pieces[them][captured as usize].clear_bit(capture_square);
+        }
 
-            // PROMOTION
-            if (self.turn == Color::White && ROW_7.is_set(from) && self.is_square_empty(to))
-                || (self.turn == Color::Black && ROW_2.is_set(from) && self.is_square_empty(to))
-            {
-                moves.push(Move {
-                    from,
-                    to,
-                    piece: Piece::Pawn,
-                    color: self.turn,
-                    en_passant: false,
-                    castling: false,
-                    promotion: Some(Piece::Queen),
-                    capture: None,
-                });
-                moves.push(Move {
-                    from,
-                    to,
-                    piece: Piece::Pawn,
-                    color: self.turn,
-                    en_passant: false,
-                    castling: false,
-                    promotion: Some(Piece::Rook),
-                    capture: None,
-                });
-                moves.push(Move {
-                    from,
-                    to,
-                    piece: Piece::Pawn,
-                    color: self.turn,
-                    en_passant: false,
-                    castling: false,
-                    promotion: Some(Piece::Bishop),
-                    capture: None,
-                });
-                moves.push(Move {
-                    from,
-                    to,
-                    piece: Piece::Pawn,
-                    color: self.turn,
-                    en_passant: false,
-                    castling: false,
-                    promotion: Some(Piece::Knight),
-                    capture: None,
-                });
-            }
+        if mv.castling {
+            let (rook_from, rook_to) = self.castling_rook_squares(mv.to);
+            pieces[us][Piece::Rook as usize].clear_bit(rook_from);
+            pieces[us][Piece::Rook as usize].set_bit(rook_to);
+        }
 
-            // NORMAL PUSH
-            if self.is_square_empty(to) {
+        pieces
+    }
+
+    fn side_occupancy(side: &[Bitboard; 6]) -> Bitboard {
+        side.iter().fold(Bitboard::new(), |acc, &bb| acc | bb)
+    }
+
+    pub fn leaves_king_safe(&self, mv: &Move) -> bool {
+        let us = mv.color as usize;
+        let them = mv.color.opposite() as usize;
+        let pieces = self.pieces_after(mv);
+
+        let Some(king) = pieces[us][Piece::King as usize].first_set_bit() else {
+            return true;
+        };
+
+        let enemies = Board::side_occupancy(&pieces[them]);
+        let occupancy = Board::side_occupancy(&pieces[us]) | enemies;
+
+        (Board::attackers_to_with(&pieces, king, occupancy) & enemies).is_empty()
+    }
+
+    pub fn gives_check(&self, mv: &Move) -> bool {
+        let us = mv.color as usize;
+        let them = mv.color.opposite() as usize;
+        let pieces = self.pieces_after(mv);
+
+        let Some(king) = self.king_square(mv.color.opposite()) else {
+            return false;
+        };
+
+        // covers direct checks and checks discovered by the moving piece
+        let friends = Board::side_occupancy(&pieces[us]);
+        let occupancy = friends | Board::side_occupancy(&pieces[them]);
+
+        !(Board::attackers_to_with(&pieces, king, occupancy) & friends).is_empty()
+    }
+
+    fn push_pawn_moves(
+        &self,
+        moves: &mut Vec<Move>,
+        from: usize,
+        to: usize,
+        capture: Option<Piece>,
+    ) {
+        // PROMOTION, queen first since under-promotions are rarely best
+        if ROW_8.is_set(to) || ROW_1.is_set(to) {
+            for promotion in [Piece::Queen, Piece::Rook, Piece::Bishop, Piece::Knight] {
                 moves.push(Move {
                     from,
                     to,
@@ -189,231 +279,183 @@ impl Board {
                     color: self.turn,
                     en_passant: false,
                     castling: false,
-                    promotion: None,
-                    capture: None,
+                    promotion: Some(promotion),
+                    capture,
                 });
             }
+            return;
         }
 
-        moves
-    }
-
-    fn generate_slider_moves(
-        &self,
-        directions: &[i32],
-        pieces: Bitboard,
-        piece: Piece,
-    ) -> Vec<Move> {
-        let mut moves = Vec::new();
-
-        for i in 0..BOARD_SIZE {
-            if !pieces.is_set(i) {
-                continue;
-            }
-
-            let from = i;
-
-            for direction in directions.iter() {
-                let mut to = from as i32 + direction;
-                while Board::is_index_in_bounds(to) {
-                    if self.is_square_empty(to as usize) {
-                        moves.push(Move {
-                            from,
-                            to: to as usize,
-                            piece,
-                            color: self.turn,
-                            en_passant: false,
-                            castling: false,
-                            promotion: None,
-                            capture: None,
-                        });
-                    } else if self.is_enemy(to as usize) {
-                        if let Some(piece_at) = self.piece_at(to as usize) {
-                            moves.push(Move {
-                                from,
-                                to: to as usize,
-                                piece,
-                                color: self.turn,
-                                en_passant: false,
-                                castling: false,
-                                promotion: None,
-                                capture: Some(piece_at.piece),
-                            });
-                        }
-                        break;
-                    } else {
-                        break;
-                    }
-
-                    if to as usize % BOARD_WIDTH == 0 || to as usize % BOARD_WIDTH == 7 {
-                        break;
-                    }
-
-                    to += direction;
-                }
-            }
-        }
-
-        moves
+        moves.push(Move {
+            from,
+            to,
+            piece: Piece::Pawn,
+            color: self.turn,
+            en_passant: false,
+            castling: false,
+            promotion: None,
+            capture,
+        });
     }
-    pub fn generate_bishop_moves(&self) -> Vec<Move> {
-        let bishops = self.pieces[self.turn as usize][Piece::Bishop as usize];
-
-        // TODO: Validate check
 
-        self.generate_slider_moves(&BISHOP_DIRECTIONS, bishops, Piece::Bishop)
+    pub fn generate_pawn_moves(&self) -> Vec<Move> {
+        self.generate_pawn_moves_to(!Bitboard::new())
     }
 
-    pub fn generate_knight_moves(&self) -> Vec<Move> {
+    fn generate_pawn_moves_to(&self, target: Bitboard) -> Vec<Move> {
         let mut moves = Vec::new();
-        let knights = self.pieces[self.turn as usize][Piece::Knight as usize];
+        let mut pawns = self.pieces[self.turn as usize][Piece::Pawn as usize];
+        let enemies = self.occupancy[self.turn.opposite() as usize];
 
-        // TODO: Validate check
+        let (direction, start_row) = match self.turn {
+            Color::White => (MOVE_UP, ROW_2),
+            Color::Black => (MOVE_DOWN, ROW_7),
+        };
 
-        for i in 0..BOARD_SIZE {
-            if !knights.is_set(i) {
+        while let Some(from) = pawns.pop_lsb() {
+            let possible_to = from as i32 + direction;
+            if !Board::is_index_in_bounds(possible_to) {
                 continue;
             }
+            let to = possible_to as usize;
 
-            let from = i;
-            for direction in KNIGHT_DIRECTIONS.iter() {
-                let to = from as i32 + direction;
-                if !Board::is_index_in_bounds(to) {
-                    continue;
-                }
-
-                if (to % BOARD_WIDTH as i32 - (from % BOARD_WIDTH) as i32).abs() > 2 {
-                    continue;
+            // NORMAL PUSH
+            if self.is_square_empty(to) {
+                if target.is_set(to) {
+                    self.push_pawn_moves(&mut moves, from, to, None);
                 }
 
-                if self.is_square_empty(to as usize) {
+                // DOUBLE PUSH
+                let double = (to as i32 + direction) as usize;
+                if start_row.is_set(from) && target.is_set(double) && self.is_square_empty(double) {
                     moves.push(Move {
                         from,
-                        to: to as usize,
-                        piece: Piece::Knight,
+                        to: double,
+                        piece: Piece::Pawn,
                         color: self.turn,
                         en_passant: false,
                         castling: false,
                         promotion: None,
                         capture: None,
                     });
-                } else if self.is_enemy(to as usize) {
-                    if let Some(piece_at) = self.piece_at(to as usize) {
-                        moves.push(Move {
-                            from,
-                            to: to as usize,
-                            piece: Piece::Knight,
-                            color: self.turn,
-                            en_passant: false,
-                            castling: false,
-                            promotion: None,
-                            capture: Some(piece_at.piece),
-                        });
-                    }
                 }
             }
-        }
-
-        moves
-    }
-
-    pub fn generate_rook_moves(&self) -> Vec<Move> {
-        let rooks = self.pieces[self.turn as usize][Piece::Rook as usize];
-
-        // TODO: Validate check
-
-        self.generate_slider_moves(&ROOK_DIRECTIONS, rooks, Piece::Rook)
-    }
-
-    pub fn generate_queen_moves(&self) -> Vec<Move> {
-        let queens = self.pieces[self.turn as usize][Piece::Queen as usize];
-
-        // TODO: Validate check
-
-        self.generate_slider_moves(&QUEEN_DIRECTIONS, queens, Piece::Queen)
-    }
-
-    pub fn generate_king_moves(&self) -> Vec<Move> {
-        let mut moves = Vec::new();
-        let king = self.pieces[self.turn as usize][Piece::King as usize];
 
-        // TODO: Validate check
-
-        for i in 0..BOARD_SIZE {
-            if !king.is_set(i) {
-                continue;
+            // CAPTURES
+            let attacks = Board::pawn_attacks(self.turn, from);
+            let mut captures = attacks & enemies & target;
+            while let Some(target) = captures.pop_lsb() {
+                let captured = self.piece_at(target).map(|piece_at| piece_at.piece);
+                self.push_pawn_moves(&mut moves, from, target, captured);
             }
 
-            let from = i;
-            for direction in KING_DIRECTIONS.iter() {
-                let to = from as i32 + direction;
-                if !Board::is_index_in_bounds(to) {
-                    continue;
-                }
-
-                if (to % BOARD_WIDTH as i32 - (from % BOARD_WIDTH) as i32).abs() > 1 {
-                    continue;
-                }
-
-                if self.is_square_empty(to as usize) {
+            // EN PASSANT
+            if let Some(ep) = self.game_state.en_passant_square {
+                if attacks.is_set(ep) && target.is_set(ep) {
                     moves.push(Move {
                         from,
-                        to: to as usize,
-                        piece: Piece::King,
+                        to: ep,
+                        piece: Piece::Pawn,
                         color: self.turn,
-                        en_passant: false,
+                        en_passant: true,
                         castling: false,
                         promotion: None,
-                        capture: None,
+                        capture: Some(Piece::Pawn),
                     });
-                } else if self.is_enemy(to as usize) {
-                    if let Some(piece_at) = self.piece_at(to as usize) {
-                        moves.push(Move {
-                            from,
-                            to: to as usize,
-                            piece: Piece::King,
-                            color: self.turn,
-                            en_passant: false,
-                            castling: false,
-                            promotion: None,
-                            capture: Some(piece_at.piece),
-                        });
-                    }
                 }
             }
+        }
 
-            let castle_index = match self.turn {
-                Color::White => 0,
-                Color::Black => 2,
-            };
+        moves
+    }
 
-            // CASTLING
-            if self.can_castle(self.turn, true) {
-                moves.push(Move {
-                    from,
-                    to: CASTLING_RIGHTS_SQUARES[castle_index][1],
-                    piece: Piece::King,
-                    color: self.turn,
-                    en_passant: false,
-                    castling: true,
-                    promotion: None,
-                    capture: None,
-                });
-            }
-            if self.can_castle(self.turn, false) {
+    fn generate_piece_moves(&self, piece: Piece, target: Bitboard) -> Vec<Move> {
+        let mut moves = Vec::new();
+        let mut pieces = self.pieces[self.turn as usize][piece as usize];
+        let occupancy = self.occupied();
+        let own = self.occupancy[self.turn as usize];
+
+        while let Some(from) = pieces.pop_lsb() {
+            let mut targets =
+                Board::piece_attacks(piece, self.turn, from, occupancy) & !own & target;
+            while let Some(to) = targets.pop_lsb() {
                 moves.push(Move {
                     from,
-                    to: CASTLING_RIGHTS_SQUARES[castle_index + 1][1],
-                    piece: Piece::King,
+                    to,
+                    piece,
                     color: self.turn,
                     en_passant: false,
-                    castling: true,
+                    castling: false,
                     promotion: None,
-                    capture: None,
+                    capture: self.piece_at(to).map(|piece_at| piece_at.piece),
                 });
             }
         }
 
         moves
     }
+
+    pub fn generate_bishop_moves(&self) -> Vec<Move> {
+        self.generate_piece_moves(Piece::Bishop, !Bitboard::new())
+    }
+
+    pub fn generate_knight_moves(&self) -> Vec<Move> {
+        self.generate_piece_moves(Piece::Knight, !Bitboard::new())
+    }
+
+    pub fn generate_rook_moves(&self) -> Vec<Move> {
+        self.generate_piece_moves(Piece::Rook, !Bitboard::new())
+    }
+
+    pub fn generate_queen_moves(&self) -> Vec<Move> {
+        self.generate_piece_moves(Piece::Queen, !Bitboard::new())
+    }
+
+    pub fn generate_king_moves(&self) -> Vec<Move> {
+        self.generate_king_moves_to(!Bitboard::new())
+    }
+
+    fn generate_king_moves_to(&self, target: Bitboard) -> Vec<Move> {
+        let mut moves = self.generate_piece_moves(Piece::King, target);
+        let king = self.pieces[self.turn as usize][Piece::King as usize];
+
+        let Some(from) = king.first_set_bit() else {
+            return moves;
+        };
+
+        let castle_index = match self.turn {
+            Color::White => 0,
+            Color::Black => 2,
+        };
+
+        // CASTLING
+        let king_side = CASTLING_RIGHTS_SQUARES[castle_index][1];
+        if target.is_set(king_side) && self.can_castle_now(self.turn, true) {
+            moves.push(Move {
+                from,
+                to: king_side,
+                piece: Piece::King,
+                color: self.turn,
+                en_passant: false,
+                castling: true,
+                promotion: None,
+                capture: None,
+            });
+        }
+        let queen_side = CASTLING_RIGHTS_SQUARES[castle_index + 1][1];
+        if target.is_set(queen_side) && self.can_castle_now(self.turn, false) {
+            moves.push(Move {
+                from,
+                to: queen_side,
+                piece: Piece::King,
+                color: self.turn,
+                en_passant: false,
+                castling: true,
+                promotion: None,
+                capture: None,
+            });
+        }
+
+        moves
+    }
 }
@@ -0,0 +1,88 @@
+use crate::board::{Board, Color, Piece};
+use crate::constants::*;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DrawReason {
+    Stalemate,
+    FiftyMoves,
+    Repetition,
+    InsufficientMaterial,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum GameOutcome {
+    WhiteWins,
+    BlackWins,
+    Draw(DrawReason),
+}
+
+impl Board {
+    pub fn is_checkmate(&self) -> bool {
+        self.is_in_check(self.turn) && !self.has_legal_move()
+    }
+
+    pub fn is_stalemate(&self) -> bool {
+        !self.is_in_check(self.turn) && !self.has_legal_move()
+    }
+
+    pub fn is_fifty_move_draw(&self) -> bool {
+        self.game_state.fifty_move_ply_count >= 100
+    }
+
+    pub fn is_insufficient_material(&self) -> bool {
+        let heavy_or_pawns = [Piece::Pawn, Piece::Rook, Piece::Queen]
+            .iter()
+            .any(|&piece| {
+                !(self.pieces[0][piece as usize] | self.pieces[1][piece as usize]).is_empty()
+            });
+        if heavy_or_pawns {
+            return false;
+        }
+
+        let knights =
+            self.pieces[0][Piece::Knight as usize] | self.pieces[1][Piece::Knight as usize];
+        let bishops =
+            self.pieces[0][Piece::Bishop as usize] | self.pieces[1][Piece::Bishop as usize];
+        let minors = (knights | bishops).count_bits();
+
+        // two knights cannot force mate against a bare king
+        let lone_knight_pair = bishops.is_empty()
+            && knights.count_bits() == 2
+            && [Color::White, Color::Black]
+                .iter()
+                .any(|&color| (self.occupancy[color as usize] & knights).count_bits() == 2);
+
+        // a lone minor piece, or bishops that all share one square color
+        minors <= 1
+            || lone_knight_pair
+            || (knights.is_empty()
+                && ((bishops & DARK_SQUARES).is_empty() || (bishops & !DARK_SQUARES).is_empty()))
+    }
+
+    pub fn is_draw(&self) -> Option<DrawReason> {
+        if !self.has_legal_move() {
+            return (!self.is_in_check(self.turn)).then_some(DrawReason::Stalemate);
+        }
+
+        if self.is_fifty_move_draw() {
+            Some(DrawReason::FiftyMoves)
+        } else if self.is_threefold_repetition() {
+            Some(DrawReason::Repetition)
+        } else if self.is_insufficient_material() {
+            Some(DrawReason::InsufficientMaterial)
+        } else {
+            None
+        }
+    }
+
+    pub fn game_result(&self) -> Option<GameOutcome> {
+        if self.is_checkmate() {
+            return Some(match self.turn {
+                Color::White => GameOutcome::BlackWins,
+                Color::Black => GameOutcome::WhiteWins,
+            });
+        }
+
+        self.is_draw().map(GameOutcome::Draw)
+    }
+}
@@ -0,0 +1,224 @@
+use crate::board::{Board, Move, Piece};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveError {
+    InvalidSan(String),
+    IllegalMove(String),
+    AmbiguousMove(String),
+    OccupiedByFriendly(String),
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoveError::InvalidSan(san) => write!(f, "invalid SAN '{}'", san),
+            MoveError::IllegalMove(san) => write!(f, "illegal move '{}'", san),
+            MoveError::AmbiguousMove(san) => write!(f, "ambiguous move '{}'", san),
+            MoveError::OccupiedByFriendly(square) => {
+                write!(f, "'{}' is occupied by a friendly piece", square)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+fn piece_to_san(piece: Piece) -> char {
+    match piece {
+        Piece::Pawn => 'P',
+        Piece::Knight => 'N',
+        Piece::Bishop => 'B',
+        Piece::Rook => 'R',
+        Piece::Queen => 'Q',
+        Piece::King => 'K',
+    }
+}
+
+fn piece_from_san(c: char) -> Option<Piece> {
+    match c {
+        'N' => Some(Piece::Knight),
+        'B' => Some(Piece::Bishop),
+        'R' => Some(Piece::Rook),
+        'Q' => Some(Piece::Queen),
+        'K' => Some(Piece::King),
+        _ => None,
+    }
+}
+
+impl Board {
+    pub fn parse_san(&self, san: &str) -> Result<Move, MoveError> {
+        let invalid = || MoveError::InvalidSan(san.to_string());
+        let text = san.trim().trim_end_matches(['+', '#', '!', '?']);
+
+        let legal_moves = self.generate_legal_moves();
+        let candidates: Vec<Move> = match text {
+            "O-O" | "0-0" | "O-O-O" | "0-0-0" => {
+                let target_file = if text.len() == 3 { 6 } else { 2 };
+                legal_moves
+                    .into_iter()
+                    .filter(|mv| mv.castling && mv.to % 8 == target_file)
+                    .collect()
+            }
+            _ => {
+                let mut chars: Vec<char> = text.chars().collect();
+
+                let piece = match chars.first().copied().and_then(piece_from_san) {
+                    Some(piece) => {
+                        chars.remove(0);
+                        piece
+                    }
+                    None => Piece::Pawn,
+                };
+
+                let mut promotion = None;
+                if piece == Piece::Pawn {
+                    if let Some(promoted) = chars.last().copied().and_then(piece_from_san) {
+                        if promoted == Piece::King {
+                            return Err(invalid());
+                        }
+                        promotion = Some(promoted);
+                        chars.pop();
+                        if chars.last() == Some(&'=') {
+                            chars.pop();
+                        }
+                    }
+                }
+
+                if chars.len() < 2 {
+                    return Err(invalid());
+                }
+                let target: String = chars.split_off(chars.len() - 2).into_iter().collect();
+                let to = Board::parse_square(&target).ok_or_else(invalid)?;
+
+                let is_capture = chars.last() == Some(&'x');
+                if is_capture {
+                    chars.pop();
+                }
+
+                let mut from_file = None;
+                let mut from_rank = None;
+                for c in chars {
+                    match c {
+                        'a'..='h' if from_file.is_none() && from_rank.is_none() => {
+                            from_file = Some(c as usize - 'a' as usize)
+                        }
+                        '1'..='8' if from_rank.is_none() => {
+                            from_rank = Some(c as usize - '1' as usize)
+                        }
+                        _ => return Err(invalid()),
+                    }
+                }
+
+                // a pawn capture always names the file it leaves from
+                if piece == Piece::Pawn && is_capture && from_file.is_none() {
+                    return Err(invalid());
+                }
+
+                legal_moves
+                    .into_iter()
+                    .filter(|mv| {
+                        mv.piece == piece
+                            && mv.to == to
+                            && !mv.castling
+                            && mv.promotion == promotion
+                            && mv.capture.is_some() == is_capture
+                            && from_file.is_none_or(|file| mv.from % 8 == file)
+                            && from_rank.is_none_or(|rank| mv.from / 8 == rank)
+                    })
+                    .collect()
+            }
+        };
+
+        match candidates.as_slice() {
+            [mv] => Ok(*mv),
+            [] => Err(MoveError::IllegalMove(san.to_string())),
+            _ => Err(MoveError::AmbiguousMove(san.to_string())),
+        }
+    }
+
+    pub fn apply_san_line(&mut self, movetext: &str) -> Result<Vec<Move>, MoveError> {
+        let mut board = self.clone();
+        let mut moves = Vec::new();
+
+        for token in movetext.split_whitespace() {
+            // move numbers may be glued to the move, as in "3...Nf6"
+            let digits = token.trim_start_matches(|c: char| c.is_ascii_digit());
+            let san = match digits.trim_start_matches('.') {
+                // digits without a following dot are not a move number, as in "0-0"
+                rest if rest.len() < digits.len() => rest,
+                _ => token,
+            };
+            if san.is_empty() || ["1-0", "0-1", "1/2-1/2", "*"].contains(&token) {
+                continue;
+            }
+
+            let mv = board.parse_san(san)?;
+            board.make_move(&mv);
+            moves.push(mv);
+        }
+
+        // only touch the board once the whole line has been resolved
+        *self = board;
+        Ok(moves)
+    }
+
+    pub fn to_san(&self, mv: &Move) -> String {
+        let mut san = String::new();
+        let target = Board::index_to_square(mv.to);
+
+        if mv.castling {
+            san.push_str(if mv.to % 8 == 6 { "O-O" } else { "O-O-O" });
+        } else if mv.piece == Piece::Pawn {
+            if mv.capture.is_some() {
+                san.push((b'a' + (mv.from % 8) as u8) as char);
+                san.push('x');
+            }
+            san.push_str(&target);
+            if let Some(promotion) = mv.promotion {
+                san.push('=');
+                san.push(piece_to_san(promotion));
+            }
+        } else {
+            san.push(piece_to_san(mv.piece));
+
+            let rivals: Vec<usize> = self
+                .generate_legal_moves()
+                .into_iter()
+                .filter(|other| {
+                    other.piece == mv.piece
+                        && other.to == mv.to
+                        && other.from != mv.from
+                        && !other.castling
+                })
+                .map(|other| other.from)
+                .collect();
+            if !rivals.is_empty() {
+                let file = (b'a' + (mv.from % 8) as u8) as char;
+                let rank = (b'1' + (mv.from / 8) as u8) as char;
+
+                if rivals.iter().all(|&from| from % 8 != mv.from % 8) {
+                    san.push(file);
+                } else if rivals.iter().all(|&from| from / 8 != mv.from / 8) {
+                    san.push(rank);
+                } else {
+                    san.push(file);
+                    san.push(rank);
+                }
+            }
+
+            if mv.capture.is_some() {
+                san.push('x');
+            }
+            san.push_str(&target);
+        }
+
+        let mut after = self.clone();
+        after.make_move(mv);
+        if after.is_in_check(after.turn) {
+            san.push(if !after.has_legal_move() { '#' } else { '+' });
+        }
+
+        san
+    }
+}
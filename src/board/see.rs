@@ -0,0 +1,166 @@
+use crate::board::{Board, Color, Move, Piece};
+use std::cmp::Reverse;
+
+pub const SEE_PIECE_VALUES: [i32; 6] = [100, 320, 320, 500, 900, 10000];
+
+impl Board {
+    pub fn see(&self, mv: &Move) -> i32 {
+        let mut gain = [0; 32];
+        let mut depth = 0;
+        let mut occupancy = self.occupied();
+        occupancy.clear_bit(mv.from);
+
+        gain[0] = mv
+            .capture
+            .map_or(0, |captured| SEE_PIECE_VALUES[captured as usize]);
+        if mv.en_passant {
+            occupancy.clear_bit(if mv.to > mv.from {
+                mv.to - 8
+            } else {
+                mv.to + 8
+            });
+        }
+
+        let mut attacker = mv.piece;
+        if let Some(promotion) = mv.promotion {
+            gain[0] +=
+                SEE_PIECE_VALUES[promotion as usize] - SEE_PIECE_VALUES[Piece::Pawn as usize];
+            attacker = promotion;
+        }
+
+        let mut side = mv.color.opposite();
+
+        loop {
+            let attackers =
+                self.attackers_to(mv.to, occupancy) & occupancy & self.occupancy[side as usize];
+
+            let next = [
+                Piece::Pawn,
+                Piece::Knight,
+                Piece::Bishop,
+                Piece::Rook,
+                Piece::Queen,
+                Piece::King,
+            ]
+            .into_iter()
+            .find_map(|piece| {
+                (attackers & self.pieces[side as usize][piece as usize])
+                    .first_set_bit()
+                    .map(|square| (piece, square))
+            });
+
+            let Some((piece, square)) = next else {
+                break;
+            };
+
+            // the king may only recapture if the square is no longer defended
+            if piece == Piece::King {
+                let mut remaining = occupancy;
+                remaining.clear_bit(square);
+                let defenders = self.attackers_to(mv.to, remaining)
+                    & remaining
+                    & self.occupancy[side.opposite() as usize];
+                if !defenders.is_empty() {
+                    break;
+                }
+            }
+
+            depth += 1;
+            gain[depth] = SEE_PIECE_VALUES[attacker as usize] - gain[depth - 1];
+
+            if depth == gain.len() - 1 {
+                break;
+            }
+
+            occupancy.clear_bit(square);
+            attacker = piece;
+            side = side.opposite();
+        }
+
+        while depth > 0 {
+            gain[depth - 1] = -(-gain[depth - 1]).max(gain[depth]);
+            depth -= 1;
+        }
+
+        gain[0]
+    }
+
+    pub fn mvv_lva(mv: &Move) -> i32 {
+        mv.capture.map_or(0, |captured| {
+            SEE_PIECE_VALUES[captured as usize] * 10 - SEE_PIECE_VALUES[mv.piece as usize] / 100
+        })
+    }
+
+    pub fn order_moves_with_see(&self, moves: &mut [Move]) {
+        let scored = self.score_moves_with_see(moves);
+        for (slot, (_, mv)) in moves.iter_mut().zip(scored) {
+            *slot = mv;
+        }
+    }
+
+    // pairs each move with its SEE value, best first
+    pub fn score_moves_with_see(&self, moves: &[Move]) -> Vec<(i32, Move)> {
+        let mut scored: Vec<(i32, Move)> = moves.iter().map(|mv| (self.see(mv), *mv)).collect();
+
+        // every key component is deterministic, so equal SEE values are
+        // always broken the same way regardless of the input order
+        scored.sort_by_key(|(see, mv)| {
+            (
+                Reverse(*see),
+                Reverse(Board::mvv_lva(mv)),
+                Reverse(
+                    mv.promotion
+                        .map_or(0, |piece| SEE_PIECE_VALUES[piece as usize]),
+                ),
+                mv.from,
+                mv.to,
+            )
+        });
+        scored
+    }
+
+    pub fn has_winning_capture(&self, color: Color) -> bool {
+        let occupancy = self.occupied();
+        let enemies = self.occupancy[color.opposite() as usize];
+
+        for piece in [
+            Piece::Pawn,
+            Piece::Knight,
+            Piece::Bishop,
+            Piece::Rook,
+            Piece::Queen,
+            Piece::King,
+        ] {
+            let mut pieces = self.pieces[color as usize][piece as usize];
+
+            while let Some(from) = pieces.pop_lsb() {
+                let mut targets = Board::piece_attacks(piece, color, from, occupancy) & enemies;
+
+                while let Some(to) = targets.pop_lsb() {
+                    let mv = Move {
+                        from,
+                        to,
+                        piece,
+                        color,
+                        en_passant: false,
+                        castling: false,
+                        promotion: None,
+                        capture: self.piece_at(to).map(|captured| captured.piece),
+                    };
+
+                    if self.see(&mv) > 0 {
+                        return true;
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    pub fn is_quiet(&self) -> bool {
+        !self.is_in_check(self.turn)
+            && !self.has_winning_capture(self.turn)
+            && !self.has_winning_capture(self.turn.opposite())
+    }
+}
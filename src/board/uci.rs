@@ -0,0 +1,110 @@
+use crate::board::{Board, Move, Piece};
+use crate::constants::CASTLING_RIGHTS_SQUARES;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveParseError {
+    // not four or five characters, or junk after the squares
+    InvalidFormat(String),
+    InvalidSquare(String),
+    InvalidPromotion(char),
+    IllegalMove(String),
+    // no piece of the side to move on the origin square
+    NotOnBoard(String),
+}
+
+impl Display for MoveParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoveParseError::InvalidFormat(mv) => write!(f, "malformed move '{}'", mv),
+            MoveParseError::InvalidSquare(square) => write!(f, "invalid square '{}'", square),
+            MoveParseError::InvalidPromotion(piece) => {
+                write!(f, "invalid promotion piece '{}'", piece)
+            }
+            MoveParseError::IllegalMove(mv) => write!(f, "illegal move '{}'", mv),
+            MoveParseError::NotOnBoard(square) => {
+                write!(f, "no piece to move on '{}'", square)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MoveParseError {}
+
+fn promotion_from_uci(c: char) -> Option<Piece> {
+    match c {
+        'n' => Some(Piece::Knight),
+        'b' => Some(Piece::Bishop),
+        'r' => Some(Piece::Rook),
+        'q' => Some(Piece::Queen),
+        _ => None,
+    }
+}
+
+impl Board {
+    pub fn parse_uci_move(&self, text: &str) -> Result<Move, MoveParseError> {
+        let text = text.trim();
+        let malformed = || MoveParseError::InvalidFormat(text.to_string());
+        if !(4..=5).contains(&text.chars().count()) {
+            return Err(malformed());
+        }
+        let square = |range: std::ops::Range<usize>| {
+            let square = text.get(range).unwrap_or("");
+            Board::parse_square(square)
+                .ok_or_else(|| MoveParseError::InvalidSquare(square.to_string()))
+        };
+        let from = square(0..2)?;
+        let to = square(2..4)?;
+
+        let promotion = match text.get(4..).and_then(|rest| rest.chars().next()) {
+            Some(c @ ('k' | 'p')) => return Err(MoveParseError::InvalidPromotion(c)),
+            Some(c) => Some(promotion_from_uci(c).ok_or_else(malformed)?),
+            None => None,
+        };
+
+        if !self.occupancy[self.turn as usize].is_set(from) {
+            return Err(MoveParseError::NotOnBoard(Board::index_to_square(from)));
+        }
+
+        let Some(moving) = self.piece_at(from) else {
+            return Err(MoveParseError::NotOnBoard(Board::index_to_square(from)));
+        };
+
+        // Chess960 castling is sent as the king capturing its own rook
+        let castle_target = (0..2)
+            .map(|side| self.turn as usize * 2 + side)
+            .find(|&index| {
+                let target = CASTLING_RIGHTS_SQUARES[index][1];
+                self.castling_rooks[index] == to
+                    && self.pieces[self.turn as usize][Piece::Rook as usize].is_set(to)
+                    || target == to && from.abs_diff(to) == 2
+            })
+            .map(|index| CASTLING_RIGHTS_SQUARES[index][1])
+            .filter(|_| moving.piece == Piece::King);
+
+        let en_passant =
+            moving.piece == Piece::Pawn && self.game_state.en_passant_square == Some(to);
+        let mv = Move {
+            from,
+            to: castle_target.unwrap_or(to),
+            piece: moving.piece,
+            color: self.turn,
+            en_passant,
+            castling: castle_target.is_some(),
+            promotion,
+            capture: if en_passant {
+                Some(Piece::Pawn)
+            } else if castle_target.is_some() {
+                None
+            } else {
+                self.piece_at(to).map(|piece_at| piece_at.piece)
+            },
+        };
+
+        if self.is_legal(&mv) {
+            Ok(mv)
+        } else {
+            Err(MoveParseError::IllegalMove(text.to_string()))
+        }
+    }
+}
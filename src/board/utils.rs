@@ -13,12 +13,39 @@ impl Board {
         row * BOARD_WIDTH + col
     }
 
+    pub const fn square_from_file_rank(file: usize, rank: usize) -> usize {
+        rank * BOARD_WIDTH + file
+    }
+
+    pub fn parse_square(square: &str) -> Option<usize> {
+        let mut chars = square.chars();
+        let (col, row) = (chars.next()?, chars.next()?);
+
+        if chars.next().is_some() || !('a'..='h').contains(&col) || !('1'..='8').contains(&row) {
+            return None;
+        }
+
+        Some(Board::square_to_index(square))
+    }
+
     pub fn index_to_square(index: usize) -> String {
         let col = (index % BOARD_WIDTH) as u8 + b'a';
         let row = (index / BOARD_WIDTH) as u8 + b'1';
         format!("{}{}", col as char, row as char)
     }
 
+    pub fn chebyshev_distance(a: usize, b: usize) -> u8 {
+        let file_distance = (a % BOARD_WIDTH).abs_diff(b % BOARD_WIDTH);
+        let rank_distance = (a / BOARD_WIDTH).abs_diff(b / BOARD_WIDTH);
+        file_distance.max(rank_distance) as u8
+    }
+
+    pub fn manhattan_distance(a: usize, b: usize) -> u8 {
+        let file_distance = (a % BOARD_WIDTH).abs_diff(b % BOARD_WIDTH);
+        let rank_distance = (a / BOARD_WIDTH).abs_diff(b / BOARD_WIDTH);
+        (file_distance + rank_distance) as u8
+    }
+
     pub fn is_square_empty(&self, index: usize) -> bool {
         !self.occupancy[Color::White as usize].is_set(index)
             && !self.occupancy[Color::Black as usize].is_set(index)
@@ -35,14 +62,7 @@ impl Board {
     pub fn piece_at(&self, index: usize) -> Option<PieceAt> {
         for &color in &[Color::White, Color::Black] {
             if self.occupancy[color as usize].is_set(index) {
-                for piece in [
-                    Piece::Pawn,
-                    Piece::Knight,
-                    Piece::Bishop,
-                    Piece::Rook,
-                    Piece::Queen,
-                    Piece::King,
-                ] {
+                for piece in Piece::all() {
                     if self.pieces[color as usize][piece as usize].is_set(index) {
                         return Some(PieceAt { piece, color });
                     }
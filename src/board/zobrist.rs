@@ -1,4 +1,4 @@
-use crate::board::{Board, Color};
+use crate::board::{Board, Color, Piece};
 use once_cell::sync::Lazy;
 use rand::{rng, Rng};
 
@@ -17,18 +17,18 @@ impl Zobrist {
         let mut en_passant = [0; 8];
         let side = rng.random();
 
-        for i in 0..12 {
-            for j in 0..64 {
-                pieces[i][j] = rng.random();
+        for piece in pieces.iter_mut() {
+            for square in piece.iter_mut() {
+                *square = rng.random();
             }
         }
 
-        for i in 0..16 {
-            castling_rights[i] = rng.random();
+        for key in castling_rights.iter_mut() {
+            *key = rng.random();
         }
 
-        for i in 0..8 {
-            en_passant[i] = rng.random();
+        for key in en_passant.iter_mut() {
+            *key = rng.random();
         }
 
         Self {
@@ -39,6 +39,10 @@ impl Zobrist {
         }
     }
 
+    pub fn piece_index(piece: Piece, color: Color) -> usize {
+        piece as usize + if color == Color::Black { 0 } else { 6 }
+    }
+
     pub fn hash(&self, board: &Board) -> u64 {
         let mut hash = 0;
         let occupancy =
@@ -47,7 +51,7 @@ impl Zobrist {
         for i in 0..64 {
             if occupancy.is_set(i) {
                 let piece = board.piece_at(i).unwrap();
-                hash ^= self.pieces[piece.piece as usize * (1 + piece.color as usize)][i];
+                hash ^= self.pieces[Zobrist::piece_index(piece.piece, piece.color)][i];
             }
         }
 
@@ -55,11 +59,7 @@ impl Zobrist {
             hash ^= self.side;
         }
 
-        for i in 0..4 {
-            if board.game_state.castling_rights & (1 << i) != 0 {
-                hash ^= self.castling_rights[i];
-            }
-        }
+        hash ^= self.castling_rights[board.game_state.castling_rights as usize];
 
         if let Some(en_passant) = board.game_state.en_passant_square {
             hash ^= self.en_passant[en_passant % 8];
@@ -24,6 +24,98 @@ pub const COL_E: Bitboard = Bitboard(0x1010101010101010);
 pub const COL_F: Bitboard = Bitboard(0x2020202020202020);
 pub const COL_G: Bitboard = Bitboard(0x4040404040404040);
 pub const COL_H: Bitboard = Bitboard(0x8080808080808080);
+pub const CENTER: Bitboard = Bitboard(0x0000001818000000);
+pub const EXTENDED_CENTER: Bitboard = Bitboard(0x00003C3C3C3C0000);
+pub const DARK_SQUARES: Bitboard = Bitboard(0xAA55AA55AA55AA55);
+
+pub const PHASE_WEIGHTS: [i32; 6] = [0, 1, 1, 2, 4, 0];
+pub const MAX_PHASE: i32 = 24;
+
+pub const KING_ATTACK_WEIGHTS: [i32; 6] = [1, 2, 2, 3, 5, 1];
+
+pub const FILES: [Bitboard; 8] = [COL_A, COL_B, COL_C, COL_D, COL_E, COL_F, COL_G, COL_H];
+pub const ADJACENT_FILES: [Bitboard; 8] = [
+    COL_B,
+    Bitboard(COL_A.0 | COL_C.0),
+    Bitboard(COL_B.0 | COL_D.0),
+    Bitboard(COL_C.0 | COL_E.0),
+    Bitboard(COL_D.0 | COL_F.0),
+    Bitboard(COL_E.0 | COL_G.0),
+    Bitboard(COL_F.0 | COL_H.0),
+    COL_G,
+];
+
+pub const A1: usize = 0;
+pub const B1: usize = 1;
+pub const C1: usize = 2;
+pub const D1: usize = 3;
+pub const E1: usize = 4;
+pub const F1: usize = 5;
+pub const G1: usize = 6;
+pub const H1: usize = 7;
+
+pub const A2: usize = 8;
+pub const B2: usize = 9;
+pub const C2: usize = 10;
+pub const D2: usize = 11;
+pub const E2: usize = 12;
+pub const F2: usize = 13;
+pub const G2: usize = 14;
+pub const H2: usize = 15;
+
+pub const A3: usize = 16;
+pub const B3: usize = 17;
+pub const C3: usize = 18;
+pub const D3: usize = 19;
+pub const E3: usize = 20;
+pub const F3: usize = 21;
+pub const G3: usize = 22;
+pub const H3: usize = 23;
+
+pub const A4: usize = 24;
+pub const B4: usize = 25;
+pub const C4: usize = 26;
+pub const D4: usize = 27;
+pub const E4: usize = 28;
+pub const F4: usize = 29;
+pub const G4: usize = 30;
+pub const H4: usize = 31;
+
+pub const A5: usize = 32;
+pub const B5: usize = 33;
+pub const C5: usize = 34;
+pub const D5: usize = 35;
+pub const E5: usize = 36;
+pub const F5: usize = 37;
+pub const G5: usize = 38;
+pub const H5: usize = 39;
+
+pub const A6: usize = 40;
+pub const B6: usize = 41;
+pub const C6: usize = 42;
+pub const D6: usize = 43;
+pub const E6: usize = 44;
+pub const F6: usize = 45;
+pub const G6: usize = 46;
+pub const H6: usize = 47;
+
+pub const A7: usize = 48;
+pub const B7: usize = 49;
+pub const C7: usize = 50;
+pub const D7: usize = 51;
+pub const E7: usize = 52;
+pub const F7: usize = 53;
+pub const G7: usize = 54;
+pub const H7: usize = 55;
+
+pub const A8: usize = 56;
+pub const B8: usize = 57;
+pub const C8: usize = 58;
+pub const D8: usize = 59;
+pub const E8: usize = 60;
+pub const F8: usize = 61;
+pub const G8: usize = 62;
+pub const H8: usize = 63;
 
 pub const STARTING_POSITION: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 
@@ -41,3 +133,4 @@ pub const CASTLING_WHITE_QUEEN: u8 = 0b0010;
 pub const CASTLING_BLACK_KING: u8 = 0b0100;
 pub const CASTLING_BLACK_QUEEN: u8 = 0b1000;
 pub const CASTLING_ROOKS: [usize; 4] = [7, 0, 63, 56];
+pub const CASTLING_ROOK_TARGETS: [usize; 4] = [5, 3, 61, 59];
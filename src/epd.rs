@@ -0,0 +1,151 @@
+use crate::board::{Board, FenError, Move, MoveError};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EpdError {
+    MissingFields(usize),
+    InvalidFen(FenError),
+    InvalidOperation(String),
+    InvalidMove(MoveError),
+}
+
+impl Display for EpdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EpdError::MissingFields(count) => write!(f, "expected 4 fields, found {}", count),
+            EpdError::InvalidFen(error) => write!(f, "invalid position: {}", error),
+            EpdError::InvalidOperation(operation) => {
+                write!(f, "invalid operation '{}'", operation)
+            }
+            EpdError::InvalidMove(error) => write!(f, "invalid move: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for EpdError {}
+
+pub struct Epd {
+    pub board: Board,
+    pub operations: HashMap<String, String>,
+    pub best_moves: Vec<Move>,
+    pub avoid_moves: Vec<Move>,
+}
+
+impl Epd {
+    pub fn parse(epd: &str) -> Result<Epd, EpdError> {
+        let mut fields = Vec::new();
+        let mut rest = epd.trim();
+        while fields.len() < 4 && !rest.is_empty() {
+            let (field, tail) = split_field(rest);
+            fields.push(field);
+            rest = tail;
+        }
+        if fields.len() < 4 {
+            return Err(EpdError::MissingFields(fields.len()));
+        }
+
+        // some EPD files carry the FEN move clocks before the operations
+        let (halfmove, tail) = split_field(rest);
+        let (fullmove, tail) = split_field(tail);
+        let is_clock = |field: &str| field.parse::<u32>().is_ok();
+        let clocks = if is_clock(halfmove) && is_clock(fullmove) {
+            rest = tail;
+            Some((halfmove, fullmove))
+        } else {
+            None
+        };
+
+        let mut operations = HashMap::new();
+        for operation in split_operations(rest)? {
+            let (opcode, operand) = operation
+                .split_once(char::is_whitespace)
+                .unwrap_or((operation.as_str(), ""));
+            if !opcode.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                return Err(EpdError::InvalidOperation(operation.clone()));
+            }
+            operations.insert(opcode.to_string(), unquote(operand.trim()).to_string());
+        }
+
+        let counter = |opcode: &str, default: &str| {
+            operations
+                .get(opcode)
+                .cloned()
+                .unwrap_or_else(|| default.to_string())
+        };
+        let (halfmove, fullmove) = match clocks {
+            Some((halfmove, fullmove)) => (halfmove.to_string(), fullmove.to_string()),
+            None => (counter("hmvc", "0"), counter("fmvn", "1")),
+        };
+        let fen = format!("{} {} {}", fields.join(" "), halfmove, fullmove);
+        let board = Board::from_fen(&fen).map_err(EpdError::InvalidFen)?;
+
+        let moves = |opcode: &str| -> Result<Vec<Move>, EpdError> {
+            operations.get(opcode).map_or(Ok(Vec::new()), |operand| {
+                operand
+                    .split_whitespace()
+                    .map(|san| board.parse_san(san).map_err(EpdError::InvalidMove))
+                    .collect()
+            })
+        };
+        let best_moves = moves("bm")?;
+        let avoid_moves = moves("am")?;
+
+        Ok(Epd {
+            board,
+            operations,
+            best_moves,
+            avoid_moves,
+        })
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.operations.get("id").map(|id| id.as_str())
+    }
+}
+
+fn split_field(text: &str) -> (&str, &str) {
+    let (field, tail) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+    (field, tail.trim_start())
+}
+
+fn split_operations(text: &str) -> Result<Vec<String>, EpdError> {
+    let mut operations = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in text.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            ';' if !in_quotes => {
+                let operation = current.trim();
+                if operation.is_empty() {
+                    return Err(EpdError::InvalidOperation(current.clone()));
+                }
+                operations.push(operation.to_string());
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(EpdError::InvalidOperation(current.trim().to_string()));
+    }
+    // the final semicolon is often left off
+    if !current.trim().is_empty() {
+        operations.push(current.trim().to_string());
+    }
+
+    Ok(operations)
+}
+
+fn unquote(operand: &str) -> &str {
+    operand
+        .strip_prefix('"')
+        .and_then(|operand| operand.strip_suffix('"'))
+        .unwrap_or(operand)
+}
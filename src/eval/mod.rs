@@ -0,0 +1,531 @@
+use crate::bitboard::Bitboard;
+use crate::board::{Board, Color, Piece};
+use crate::constants::*;
+use std::cell::RefCell;
+use std::fmt::Display;
+
+pub mod pst;
+
+// scores stay within [NEG_MATE_SCORE, MATE_SCORE], far from the ends of
+// i32, so negating a score in negamax can never overflow
+pub type Score = i32;
+
+pub const MATE_SCORE: Score = 32_000;
+pub const NEG_MATE_SCORE: Score = -MATE_SCORE;
+const _: () = assert!(NEG_MATE_SCORE > Score::MIN && MATE_SCORE < Score::MAX);
+
+pub const PIECE_VALUES: [Score; 6] = [100, 320, 320, 500, 900, 0];
+pub const BISHOP_PAIR_MG: Score = 25;
+pub const BISHOP_PAIR_EG: Score = 50;
+
+pub const ROOK_OPEN_FILE_MG: Score = 40;
+pub const ROOK_OPEN_FILE_EG: Score = 20;
+pub const ROOK_SEMI_OPEN_FILE_MG: Score = 20;
+pub const ROOK_SEMI_OPEN_FILE_EG: Score = 10;
+pub const ROOK_SEVENTH_RANK_MG: Score = 20;
+pub const ROOK_SEVENTH_RANK_EG: Score = 40;
+
+pub const MOBILITY_MG: [Score; 6] = [0, 4, 5, 2, 1, 0];
+pub const MOBILITY_EG: [Score; 6] = [0, 4, 5, 4, 2, 0];
+
+// cheap pieces hold the center more firmly, extended center counts on top
+pub const CENTER_CONTROL_MG: [Score; 6] = [6, 4, 4, 2, 1, 0];
+pub const EXTENDED_CENTER_CONTROL_MG: [Score; 6] = [2, 2, 2, 1, 0, 0];
+
+pub const KNIGHT_OUTPOST_MG: Score = 25;
+pub const KNIGHT_OUTPOST_EG: Score = 15;
+pub const BAD_BISHOP_PAWN_MG: Score = -3;
+pub const BAD_BISHOP_PAWN_EG: Score = -5;
+
+pub const DOUBLED_PAWN_MG: Score = -10;
+pub const DOUBLED_PAWN_EG: Score = -20;
+pub const ISOLATED_PAWN_MG: Score = -10;
+pub const ISOLATED_PAWN_EG: Score = -15;
+pub const PASSED_PAWN_MG: [Score; 8] = [0, 5, 10, 15, 25, 40, 60, 0];
+pub const PASSED_PAWN_EG: [Score; 8] = [0, 10, 15, 25, 40, 65, 100, 0];
+
+pub const PAWN_SHIELD_MISSING_MG: Score = -25;
+pub const PAWN_SHIELD_ADVANCED_MG: Score = -10;
+pub const KING_OPEN_FILE_MG: Score = -25;
+pub const KING_SEMI_OPEN_FILE_MG: Score = -10;
+pub const KING_ZONE_ATTACK_MG: Score = -5;
+
+pub const KING_PAWN_PROXIMITY_EG: Score = 4;
+pub const KING_MATING_PROXIMITY_EG: Score = 10;
+
+// pawnless edges smaller than a minor piece are divided by this
+pub const PAWNLESS_DRAW_DIVISOR: Score = 4;
+
+pub const CONTEMPT_TAPER_MOVES: u32 = 80;
+
+pub fn tapered_contempt(contempt: Score, fullmove: u32) -> Score {
+    let remaining = CONTEMPT_TAPER_MOVES.saturating_sub(fullmove.saturating_sub(1));
+    contempt * remaining as Score / CONTEMPT_TAPER_MOVES as Score
+}
+
+pub fn draw_score(board: &Board, contempt: Score) -> Score {
+    -tapered_contempt(contempt, board.ply / 2 + 1)
+}
+
+pub trait Evaluator {
+    fn evaluate(&self, board: &Board) -> Score;
+}
+
+pub const PAWN_CACHE_SIZE: usize = 1 << 14;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct EvalTrace {
+    pub material: Score,
+    pub piece_squares: Score,
+    pub mobility: Score,
+    pub center_control: Score,
+    pub pawns: Score,
+    pub king_safety: Score,
+    pub bishop_pair: Score,
+    pub minor_pieces: Score,
+    pub rooks: Score,
+    pub king_proximity: Score,
+    pub scaling: Score,
+    pub total: Score,
+}
+
+impl EvalTrace {
+    pub fn components(&self) -> [(&'static str, Score); 10] {
+        [
+            ("material", self.material),
+            ("piece squares", self.piece_squares),
+            ("mobility", self.mobility),
+            ("center control", self.center_control),
+            ("pawns", self.pawns),
+            ("king safety", self.king_safety),
+            ("bishop pair", self.bishop_pair),
+            ("minor pieces", self.minor_pieces),
+            ("rooks", self.rooks),
+            ("king proximity", self.king_proximity),
+        ]
+    }
+}
+
+impl Display for EvalTrace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, score) in self.components() {
+            writeln!(f, "{:<16}{:>6}", name, score)?;
+        }
+        writeln!(f, "{:<16}{:>6}", "scaling", self.scaling)?;
+        write!(f, "{:<16}{:>6}", "total", self.total)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct PawnEntry {
+    white: Bitboard,
+    black: Bitboard,
+    mg: Score,
+    eg: Score,
+}
+
+// both sides can never have pawns on every square, so this never matches
+const EMPTY_PAWN_ENTRY: PawnEntry = PawnEntry {
+    white: Bitboard(u64::MAX),
+    black: Bitboard(u64::MAX),
+    mg: 0,
+    eg: 0,
+};
+
+#[derive(Debug, Clone)]
+pub struct SimpleEvaluator {
+    pawn_cache: RefCell<Vec<PawnEntry>>,
+}
+
+impl Default for SimpleEvaluator {
+    fn default() -> Self {
+        SimpleEvaluator::new()
+    }
+}
+
+impl SimpleEvaluator {
+    pub fn new() -> Self {
+        SimpleEvaluator {
+            pawn_cache: RefCell::new(vec![EMPTY_PAWN_ENTRY; PAWN_CACHE_SIZE]),
+        }
+    }
+
+    pub fn taper(mg: Score, eg: Score, phase: i32) -> Score {
+        (mg * phase + eg * (MAX_PHASE - phase)) / MAX_PHASE
+    }
+
+    fn material(board: &Board, color: Color) -> Score {
+        PIECE_VALUES
+            .iter()
+            .enumerate()
+            .map(|(piece, value)| board.material_counts[color as usize][piece] as Score * value)
+            .sum()
+    }
+
+    fn piece_squares(board: &Board, color: Color, phase: i32) -> Score {
+        let (mut mg, mut eg) = (0, 0);
+
+        for (piece, &pieces) in board.pieces[color as usize].iter().enumerate() {
+            let mut pieces = pieces;
+            while let Some(square) = pieces.pop_lsb() {
+                let index = match color {
+                    Color::White => square ^ 56,
+                    Color::Black => square,
+                };
+                mg += pst::MG_PST[piece][index];
+                eg += pst::EG_PST[piece][index];
+            }
+        }
+
+        SimpleEvaluator::taper(mg, eg, phase)
+    }
+
+    fn mobility(board: &Board, color: Color, phase: i32) -> Score {
+        let occupancy = board.occupied();
+        let own = board.occupancy[color as usize];
+        let (mut mg, mut eg) = (0, 0);
+
+        for piece in [Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen] {
+            let mut pieces = board.pieces[color as usize][piece as usize];
+            while let Some(square) = pieces.pop_lsb() {
+                let moves = (Board::piece_attacks(piece, color, square, occupancy) & !own)
+                    .count_bits() as Score;
+                mg += moves * MOBILITY_MG[piece as usize];
+                eg += moves * MOBILITY_EG[piece as usize];
+            }
+        }
+
+        SimpleEvaluator::taper(mg, eg, phase)
+    }
+
+    fn center_control(board: &Board, color: Color, phase: i32) -> Score {
+        let occupancy = board.occupied();
+        let mut mg = 0;
+
+        for piece in [
+            Piece::Pawn,
+            Piece::Knight,
+            Piece::Bishop,
+            Piece::Rook,
+            Piece::Queen,
+        ] {
+            let mut pieces = board.pieces[color as usize][piece as usize];
+            while let Some(square) = pieces.pop_lsb() {
+                let attacks = Board::piece_attacks(piece, color, square, occupancy);
+                mg += (attacks & CENTER).count_bits() as Score * CENTER_CONTROL_MG[piece as usize];
+                mg += (attacks & EXTENDED_CENTER).count_bits() as Score
+                    * EXTENDED_CENTER_CONTROL_MG[piece as usize];
+            }
+        }
+
+        SimpleEvaluator::taper(mg, 0, phase)
+    }
+
+    fn bishop_pair(board: &Board, color: Color, phase: i32) -> Score {
+        if board.pieces[color as usize][Piece::Bishop as usize].count_bits() >= 2 {
+            SimpleEvaluator::taper(BISHOP_PAIR_MG, BISHOP_PAIR_EG, phase)
+        } else {
+            0
+        }
+    }
+
+    fn rooks(board: &Board, color: Color, phase: i32) -> Score {
+        let us = color as usize;
+        let them = color.opposite() as usize;
+        let own_pawn_files = board.pieces[us][Piece::Pawn as usize].file_fill();
+        let enemy_pawn_files = board.pieces[them][Piece::Pawn as usize].file_fill();
+        let (seventh_rank, eighth_rank) = match color {
+            Color::White => (ROW_7, ROW_8),
+            Color::Black => (ROW_2, ROW_1),
+        };
+
+        // the 7th rank only matters when it confines the king or holds pawns
+        let seventh_rank_targets = !(board.pieces[them][Piece::King as usize] & eighth_rank)
+            .is_empty()
+            || !(board.pieces[them][Piece::Pawn as usize] & seventh_rank).is_empty();
+
+        let mut score = 0;
+        let mut rooks = board.pieces[us][Piece::Rook as usize];
+
+        while let Some(square) = rooks.pop_lsb() {
+            let rook = Bitboard::from_index(square);
+
+            if (rook & own_pawn_files).is_empty() {
+                score += if (rook & enemy_pawn_files).is_empty() {
+                    SimpleEvaluator::taper(ROOK_OPEN_FILE_MG, ROOK_OPEN_FILE_EG, phase)
+                } else {
+                    SimpleEvaluator::taper(ROOK_SEMI_OPEN_FILE_MG, ROOK_SEMI_OPEN_FILE_EG, phase)
+                };
+            }
+
+            if seventh_rank_targets && !(rook & seventh_rank).is_empty() {
+                score += SimpleEvaluator::taper(ROOK_SEVENTH_RANK_MG, ROOK_SEVENTH_RANK_EG, phase);
+            }
+        }
+
+        score
+    }
+
+    fn passed_pawns(board: &Board, color: Color) -> Bitboard {
+        let pawns = board.pieces[color as usize][Piece::Pawn as usize];
+        let enemy_pawns = board.pieces[color.opposite() as usize][Piece::Pawn as usize];
+
+        // squares an enemy pawn can still block or capture on as it advances
+        let span = match color {
+            Color::White => (enemy_pawns >> 8).south_fill(),
+            Color::Black => (enemy_pawns << 8).north_fill(),
+        };
+        let front_span = span | ((span & !COL_A) >> 1) | ((span & !COL_H) << 1);
+
+        pawns & !front_span
+    }
+
+    // squares the pawns can attack now or after advancing
+    fn pawn_attack_span(pawns: Bitboard, color: Color) -> Bitboard {
+        let span = match color {
+            Color::White => (pawns << 8).north_fill(),
+            Color::Black => (pawns >> 8).south_fill(),
+        };
+
+        ((span & !COL_A) >> 1) | ((span & !COL_H) << 1)
+    }
+
+    fn minor_pieces(board: &Board, color: Color, phase: i32) -> Score {
+        let own_pawns = board.pieces[color as usize][Piece::Pawn as usize];
+        let enemy_pawns = board.pieces[color.opposite() as usize][Piece::Pawn as usize];
+        let (mut mg, mut eg) = (0, 0);
+
+        // pawn-defended squares in the enemy half no enemy pawn can contest
+        let outpost_ranks = match color {
+            Color::White => ROW_4 | ROW_5 | ROW_6,
+            Color::Black => ROW_3 | ROW_4 | ROW_5,
+        };
+        let safe =
+            outpost_ranks & !SimpleEvaluator::pawn_attack_span(enemy_pawns, color.opposite());
+        let mut knights = board.pieces[color as usize][Piece::Knight as usize] & safe;
+        while let Some(square) = knights.pop_lsb() {
+            if !(Board::pawn_attacks(color.opposite(), square) & own_pawns).is_empty() {
+                mg += KNIGHT_OUTPOST_MG;
+                eg += KNIGHT_OUTPOST_EG;
+            }
+        }
+
+        // own pawns fixed on the bishop's color restrict it
+        let occupied = board.occupied();
+        let blocked = own_pawns
+            & match color {
+                Color::White => occupied >> 8,
+                Color::Black => occupied << 8,
+            };
+        let mut bishops = board.pieces[color as usize][Piece::Bishop as usize];
+        while let Some(square) = bishops.pop_lsb() {
+            let complex = if DARK_SQUARES.is_set(square) {
+                DARK_SQUARES
+            } else {
+                !DARK_SQUARES
+            };
+            let blockers = (blocked & complex).count_bits() as Score;
+            mg += blockers * BAD_BISHOP_PAWN_MG;
+            eg += blockers * BAD_BISHOP_PAWN_EG;
+        }
+
+        SimpleEvaluator::taper(mg, eg, phase)
+    }
+
+    // white minus black, cached by pawn placement since pawns rarely move
+    fn pawn_structure(&self, board: &Board, phase: i32) -> Score {
+        let white = board.pieces[Color::White as usize][Piece::Pawn as usize];
+        let black = board.pieces[Color::Black as usize][Piece::Pawn as usize];
+        let key = (white.0 ^ black.0.rotate_left(32)).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let index = (key >> 32) as usize % PAWN_CACHE_SIZE;
+
+        let mut cache = self.pawn_cache.borrow_mut();
+        let entry = &mut cache[index];
+        if entry.white != white || entry.black != black {
+            let (white_mg, white_eg) = SimpleEvaluator::pawn_terms(board, Color::White);
+            let (black_mg, black_eg) = SimpleEvaluator::pawn_terms(board, Color::Black);
+            *entry = PawnEntry {
+                white,
+                black,
+                mg: white_mg - black_mg,
+                eg: white_eg - black_eg,
+            };
+        }
+
+        SimpleEvaluator::taper(entry.mg, entry.eg, phase)
+    }
+
+    fn pawn_terms(board: &Board, color: Color) -> (Score, Score) {
+        let pawns = board.pieces[color as usize][Piece::Pawn as usize];
+        let (mut mg, mut eg) = (0, 0);
+
+        for (file, &file_mask) in FILES.iter().enumerate() {
+            let on_file = (pawns & file_mask).count_bits() as Score;
+            if on_file == 0 {
+                continue;
+            }
+
+            if on_file > 1 {
+                mg += (on_file - 1) * DOUBLED_PAWN_MG;
+                eg += (on_file - 1) * DOUBLED_PAWN_EG;
+            }
+            if (pawns & ADJACENT_FILES[file]).is_empty() {
+                mg += on_file * ISOLATED_PAWN_MG;
+                eg += on_file * ISOLATED_PAWN_EG;
+            }
+        }
+
+        let mut passed = SimpleEvaluator::passed_pawns(board, color);
+        while let Some(square) = passed.pop_lsb() {
+            let rank = match color {
+                Color::White => square / BOARD_WIDTH,
+                Color::Black => BOARD_WIDTH - 1 - square / BOARD_WIDTH,
+            };
+            mg += PASSED_PAWN_MG[rank];
+            eg += PASSED_PAWN_EG[rank];
+        }
+
+        (mg, eg)
+    }
+
+    fn king_safety(board: &Board, color: Color, phase: i32) -> Score {
+        let own_pawns = board.pieces[color as usize][Piece::Pawn as usize];
+        let enemy_pawns = board.pieces[color.opposite() as usize][Piece::Pawn as usize];
+        let Some(king) = board.king_square(color) else {
+            return 0;
+        };
+
+        let forward = match color {
+            Color::White => 1,
+            Color::Black => -1,
+        };
+        let king_file = king % BOARD_WIDTH;
+        let king_rank = (king / BOARD_WIDTH) as i32;
+        let shield_square = |file: usize, distance: i32| {
+            let rank = king_rank + forward * distance;
+            (0..BOARD_WIDTH as i32)
+                .contains(&rank)
+                .then(|| rank as usize * BOARD_WIDTH + file)
+        };
+
+        let mut score = 0;
+        let first_file = king_file.saturating_sub(1);
+        let last_file = (king_file + 1).min(BOARD_WIDTH - 1);
+        for (file, &file_mask) in FILES
+            .iter()
+            .enumerate()
+            .take(last_file + 1)
+            .skip(first_file)
+        {
+            let is_own_pawn = |square: Option<usize>| square.is_some_and(|sq| own_pawns.is_set(sq));
+            if is_own_pawn(shield_square(file, 2)) && !is_own_pawn(shield_square(file, 1)) {
+                score += PAWN_SHIELD_ADVANCED_MG;
+            } else if !is_own_pawn(shield_square(file, 1)) {
+                score += PAWN_SHIELD_MISSING_MG;
+            }
+
+            if (own_pawns & file_mask).is_empty() {
+                score += if (enemy_pawns & file_mask).is_empty() {
+                    KING_OPEN_FILE_MG
+                } else {
+                    KING_SEMI_OPEN_FILE_MG
+                };
+            }
+        }
+
+        // a lone attacker is rarely dangerous, only count coordinated attacks
+        let (attackers, weight) =
+            board.attackers_in_region(board.king_ring(color), color.opposite());
+        if attackers >= 2 {
+            score += weight * KING_ZONE_ATTACK_MG;
+        }
+
+        SimpleEvaluator::taper(score, 0, phase)
+    }
+
+    fn king_proximity(board: &Board, color: Color, phase: i32) -> Score {
+        let us = color as usize;
+        let them = color.opposite() as usize;
+        let Some(king) = board.king_square(color) else {
+            return 0;
+        };
+
+        let mut score = 0;
+        let mut pawns = SimpleEvaluator::passed_pawns(board, color)
+            | SimpleEvaluator::passed_pawns(board, color.opposite());
+        while let Some(pawn) = pawns.pop_lsb() {
+            score += (7 - Board::chebyshev_distance(king, pawn) as Score) * KING_PAWN_PROXIMITY_EG;
+        }
+
+        // against a bare king the attacking king has to help deliver mate
+        let enemy_king = board.pieces[them][Piece::King as usize];
+        let has_mating_material = [Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen]
+            .iter()
+            .any(|&piece| !board.pieces[us][piece as usize].is_empty());
+        if board.occupancy[them] == enemy_king && has_mating_material {
+            if let Some(enemy_king) = enemy_king.first_set_bit() {
+                score += (14 - Board::manhattan_distance(king, enemy_king) as Score)
+                    * KING_MATING_PROXIMITY_EG;
+            }
+        }
+
+        SimpleEvaluator::taper(0, score, phase)
+    }
+
+    fn draw_scale(board: &Board, score: Score) -> Score {
+        let edge = SimpleEvaluator::material(board, Color::White)
+            - SimpleEvaluator::material(board, Color::Black);
+        let stronger = match edge.signum() {
+            1 => Color::White,
+            -1 => Color::Black,
+            _ if score > 0 => Color::White,
+            _ => Color::Black,
+        };
+
+        let has_pawns = !board.pieces[stronger as usize][Piece::Pawn as usize].is_empty();
+        if !has_pawns && edge.abs() < PIECE_VALUES[Piece::Bishop as usize] {
+            score / PAWNLESS_DRAW_DIVISOR
+        } else {
+            score
+        }
+    }
+
+    // every component is white minus black and they sum to the total
+    pub fn evaluate_trace(&self, board: &Board) -> EvalTrace {
+        let phase = board.game_phase() as i32;
+        let term = |side: fn(&Board, Color, i32) -> Score| {
+            side(board, Color::White, phase) - side(board, Color::Black, phase)
+        };
+
+        let mut trace = EvalTrace {
+            material: SimpleEvaluator::material(board, Color::White)
+                - SimpleEvaluator::material(board, Color::Black),
+            piece_squares: term(SimpleEvaluator::piece_squares),
+            mobility: term(SimpleEvaluator::mobility),
+            center_control: term(SimpleEvaluator::center_control),
+            pawns: self.pawn_structure(board, phase),
+            king_safety: term(SimpleEvaluator::king_safety),
+            bishop_pair: term(SimpleEvaluator::bishop_pair),
+            minor_pieces: term(SimpleEvaluator::minor_pieces),
+            rooks: term(SimpleEvaluator::rooks),
+            king_proximity: term(SimpleEvaluator::king_proximity),
+            ..EvalTrace::default()
+        };
+
+        let unscaled = trace.components().iter().map(|(_, score)| score).sum();
+        trace.total = SimpleEvaluator::draw_scale(board, unscaled);
+        trace.scaling = trace.total - unscaled;
+        trace
+    }
+}
+
+impl Evaluator for SimpleEvaluator {
+    fn evaluate(&self, board: &Board) -> Score {
+        let score = self.evaluate_trace(board).total;
+
+        match board.turn {
+            Color::White => score,
+            Color::Black => -score,
+        }
+    }
+}
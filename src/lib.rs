@@ -1,3 +1,8 @@
 pub mod bitboard;
 pub mod board;
 pub mod constants;
+pub mod epd;
+pub mod eval;
+pub mod movegen;
+pub mod perft;
+pub mod pgn;
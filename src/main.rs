@@ -1,10 +1,95 @@
 use aether::board::Board;
+use aether::eval::SimpleEvaluator;
+use aether::perft::PerftState;
+use std::env;
+use std::process;
+use std::time::Instant;
+
+fn usage() -> ! {
+    eprintln!("usage: aether perft <depth> [--fen FEN] [--stats] [--threads N]");
+    eprintln!("       aether eval <fen>");
+    process::exit(1);
+}
+
+fn perft(args: &[String]) {
+    let Some(depth) = args.first().and_then(|depth| depth.parse().ok()) else {
+        usage();
+    };
+    // the start position unless a FEN is given as a single quoted argument
+    let mut state = match args.iter().position(|arg| arg == "--fen") {
+        Some(index) => match args.get(index + 1).map(|fen| PerftState::from_fen(fen)) {
+            Some(Ok(state)) => state,
+            Some(Err(error)) => {
+                eprintln!("invalid FEN: {}", error);
+                process::exit(1);
+            }
+            None => usage(),
+        },
+        None => PerftState::from_board(&Board::init()),
+    };
+    let threads = match args.iter().position(|arg| arg == "--threads") {
+        Some(index) => match args.get(index + 1).and_then(|threads| threads.parse().ok()) {
+            Some(threads) => threads,
+            None => usage(),
+        },
+        None => 1,
+    };
+
+    if args[1..].iter().any(|arg| arg == "--stats") {
+        if args.iter().any(|arg| arg == "--threads") {
+            eprintln!("--stats runs on a single thread and cannot be combined with --threads");
+            process::exit(1);
+        }
+
+        let stats = state.perft_stats(depth);
+        println!("nodes       {}", stats.nodes);
+        println!("captures    {}", stats.captures);
+        println!("en passant  {}", stats.en_passant);
+        println!("castles     {}", stats.castles);
+        println!("promotions  {}", stats.promotions);
+        println!("checks      {}", stats.checks);
+        println!("checkmates  {}", stats.checkmates);
+    } else {
+        let start = Instant::now();
+        let nodes = state.perft_parallel(depth, threads);
+        let elapsed = start.elapsed();
+
+        println!("nodes       {}", nodes);
+        println!("time        {} ms", elapsed.as_millis());
+        println!(
+            "nps         {}",
+            (nodes as f64 / elapsed.as_secs_f64()) as u64
+        );
+    }
+}
+
+fn eval(args: &[String]) {
+    let board = match Board::from_fen(&args.join(" ")) {
+        Ok(board) => board,
+        Err(error) => {
+            eprintln!("invalid FEN: {}", error);
+            process::exit(1);
+        }
+    };
+
+    // from white's point of view, whoever is to move
+    println!("{}", SimpleEvaluator::new().evaluate_trace(&board));
+}
 
 fn main() {
-    let mut board = Board::init();
-    board.print();
-    let _ = board.generate_possible_moves();
-    board.set_fen("rnbqkbnr/pppp1ppp/8/4q3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1");
-    board.print();
-    board.generate_possible_moves();
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(|command| command.as_str()) {
+        Some("perft") => perft(&args[1..]),
+        Some("eval") => eval(&args[1..]),
+        Some(_) => usage(),
+        None => {
+            let mut board = Board::init();
+            board.print();
+            let _ = board.generate_possible_moves();
+            board.set_fen("rnbqkbnr/pppp1ppp/8/4q3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1");
+            board.print();
+            board.generate_possible_moves();
+        }
+    }
 }
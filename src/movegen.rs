@@ -0,0 +1,214 @@
+use crate::bitboard::Bitboard;
+use crate::board::{Board, Color, Move, Piece};
+use std::ops::{Deref, DerefMut};
+
+/// Upper bound on legal moves in any reachable position (218) with room to spare.
+pub const MAX_MOVES: usize = 256;
+
+const EMPTY_MOVE: Move = Move {
+    from: 0,
+    to: 0,
+    piece: Piece::Pawn,
+    color: Color::White,
+    en_passant: false,
+    castling: false,
+    promotion: None,
+    capture: None,
+};
+
+/// Fills `moves` with every pseudo-legal move for the side to move.
+pub fn pseudo_legal(board: &Board, moves: &mut Vec<Move>) {
+    moves.clear();
+    moves.extend(board.generate_pseudo_legal_moves());
+}
+
+/// Fills `moves` with every legal move for the side to move.
+pub fn legal(board: &Board, moves: &mut Vec<Move>) {
+    moves.clear();
+    moves.extend(board.generate_legal_moves());
+}
+
+/// Fills `moves` with the legal moves landing on a square in `target`;
+/// other destinations are never generated.
+pub fn legal_to(board: &Board, target: Bitboard, moves: &mut Vec<Move>) {
+    moves.clear();
+    moves.extend(board.generate_legal_moves_to(target));
+}
+
+/// Fills `moves` with the legal captures, including en passant.
+pub fn captures(board: &Board, moves: &mut Vec<Move>) {
+    moves.clear();
+    moves.extend(board.generate_legal_captures());
+}
+
+/// Fills `moves` with the legal non-captures that give check, direct or
+/// discovered.
+pub fn checks(board: &Board, moves: &mut Vec<Move>) {
+    moves.clear();
+    moves.extend(
+        board
+            .generate_legal_moves()
+            .into_iter()
+            .filter(|mv| mv.capture.is_none() && board.gives_check(mv)),
+    );
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Stage {
+    GenerateCaptures,
+    GoodCaptures,
+    GenerateQuiets,
+    Quiets,
+    BadCaptures,
+    Done,
+}
+
+/// Hands out the legal moves one at a time: captures that win or trade
+/// material by SEE, then quiet moves, then captures that lose material.
+/// Quiet moves are only generated once the winning captures run out.
+pub struct MovePicker {
+    stage: Stage,
+    good_captures: Vec<Move>,
+    quiets: Vec<Move>,
+    bad_captures: Vec<Move>,
+}
+
+impl Default for MovePicker {
+    fn default() -> Self {
+        MovePicker::new()
+    }
+}
+
+impl MovePicker {
+    pub fn new() -> Self {
+        MovePicker {
+            stage: Stage::GenerateCaptures,
+            good_captures: Vec::new(),
+            quiets: Vec::new(),
+            bad_captures: Vec::new(),
+        }
+    }
+
+    pub fn next(&mut self, board: &Board) -> Option<Move> {
+        loop {
+            let moves = match self.stage {
+                Stage::GenerateCaptures => {
+                    self.generate_captures(board);
+                    self.stage = Stage::GoodCaptures;
+                    continue;
+                }
+                Stage::GenerateQuiets => {
+                    self.generate_quiets(board);
+                    self.stage = Stage::Quiets;
+                    continue;
+                }
+                Stage::GoodCaptures => &mut self.good_captures,
+                Stage::Quiets => &mut self.quiets,
+                Stage::BadCaptures => &mut self.bad_captures,
+                Stage::Done => return None,
+            };
+
+            // each stage is stored best last so moves pop off in order
+            if let Some(mv) = moves.pop() {
+                return Some(mv);
+            }
+
+            self.stage = match self.stage {
+                Stage::GoodCaptures => Stage::GenerateQuiets,
+                Stage::Quiets => Stage::BadCaptures,
+                _ => Stage::Done,
+            };
+        }
+    }
+
+    fn generate_captures(&mut self, board: &Board) {
+        let mut target = board.occupancy[board.turn.opposite() as usize];
+        if let Some(en_passant) = board.game_state.en_passant_square {
+            target.set_bit(en_passant);
+        }
+        let captures: Vec<Move> = board
+            .generate_legal_moves_to(target)
+            .into_iter()
+            .filter(|mv| mv.capture.is_some())
+            .collect();
+
+        // split on the SEE values computed for the ordering
+        let (good, bad): (Vec<_>, Vec<_>) = board
+            .score_moves_with_see(&captures)
+            .into_iter()
+            .partition(|&(see, _)| see >= 0);
+        self.good_captures = good.into_iter().rev().map(|(_, mv)| mv).collect();
+        self.bad_captures = bad.into_iter().rev().map(|(_, mv)| mv).collect();
+    }
+
+    fn generate_quiets(&mut self, board: &Board) {
+        // a Chess960 castle may land the king on its own rook or leave it in place
+        let target = !board.occupancy[board.turn.opposite() as usize];
+        self.quiets = board
+            .generate_legal_moves_to(target)
+            .into_iter()
+            .filter(|mv| mv.capture.is_none())
+            .rev()
+            .collect();
+    }
+}
+
+/// Fixed-capacity move buffer stored inline, for callers that want to keep
+/// a move list without an allocation of its own. The generators still
+/// return `Vec`s, so filling one copies the moves over.
+#[derive(Clone)]
+pub struct MoveList {
+    moves: [Move; MAX_MOVES],
+    len: usize,
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        MoveList::new()
+    }
+}
+
+impl MoveList {
+    pub fn new() -> Self {
+        MoveList {
+            moves: [EMPTY_MOVE; MAX_MOVES],
+            len: 0,
+        }
+    }
+
+    pub fn push(&mut self, mv: Move) {
+        debug_assert!(
+            self.len < MAX_MOVES,
+            "MoveList overflow: more than {} moves",
+            MAX_MOVES
+        );
+        self.moves[self.len] = mv;
+        self.len += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &[Move] {
+        &self.moves[..self.len]
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut [Move] {
+        &mut self.moves[..self.len]
+    }
+}
+
+impl Extend<Move> for MoveList {
+    fn extend<I: IntoIterator<Item = Move>>(&mut self, moves: I) {
+        for mv in moves {
+            self.push(mv);
+        }
+    }
+}
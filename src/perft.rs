@@ -0,0 +1,164 @@
+use crate::board::{Board, FenError};
+use std::thread;
+
+const PERFT_TABLE_SIZE: usize = 1 << 18;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct PerftStats {
+    pub nodes: u64,
+    pub captures: u64,
+    pub en_passant: u64,
+    pub castles: u64,
+    pub promotions: u64,
+    pub checks: u64,
+    pub checkmates: u64,
+}
+
+impl PerftStats {
+    fn add(&mut self, other: &PerftStats) {
+        self.nodes += other.nodes;
+        self.captures += other.captures;
+        self.en_passant += other.en_passant;
+        self.castles += other.castles;
+        self.promotions += other.promotions;
+        self.checks += other.checks;
+        self.checkmates += other.checkmates;
+    }
+}
+
+#[derive(Copy, Clone, Default)]
+struct PerftEntry {
+    key: u64,
+    depth: u32,
+    nodes: u64,
+}
+
+pub struct PerftState {
+    pub board: Board,
+}
+
+impl PerftState {
+    pub fn from_board(board: &Board) -> Self {
+        PerftState {
+            board: board.clone(),
+        }
+    }
+
+    pub fn from_fen(fen: &str) -> Result<Self, FenError> {
+        Ok(PerftState {
+            board: Board::from_fen(fen)?,
+        })
+    }
+
+    pub fn perft(&mut self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let moves = self.board.generate_legal_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        let mut nodes = 0;
+        for mv in moves.iter() {
+            self.board.make_move(mv);
+            nodes += self.perft(depth - 1);
+            self.board.undo_move(mv);
+        }
+
+        nodes
+    }
+
+    pub fn perft_hashed(&mut self, depth: u32) -> u64 {
+        let mut table = vec![PerftEntry::default(); PERFT_TABLE_SIZE];
+        self.perft_with_table(depth, &mut table)
+    }
+
+    fn perft_with_table(&mut self, depth: u32, table: &mut [PerftEntry]) -> u64 {
+        // leaf counts are cheaper to generate than to look up
+        if depth <= 1 {
+            return self.perft(depth);
+        }
+
+        let key = self.board.game_state.current_zobrist;
+        let index = (key ^ depth as u64) as usize % table.len();
+        let entry = table[index];
+        if entry.key == key && entry.depth == depth {
+            return entry.nodes;
+        }
+
+        let mut nodes = 0;
+        for mv in self.board.generate_legal_moves().iter() {
+            self.board.make_move(mv);
+            nodes += self.perft_with_table(depth - 1, table);
+            self.board.undo_move(mv);
+        }
+
+        table[index] = PerftEntry { key, depth, nodes };
+        nodes
+    }
+
+    pub fn perft_stats(&mut self, depth: u32) -> PerftStats {
+        let mut stats = PerftStats::default();
+        if depth == 0 {
+            stats.nodes = 1;
+            return stats;
+        }
+
+        for mv in self.board.generate_legal_moves().iter() {
+            self.board.make_move(mv);
+
+            if depth == 1 {
+                // categories describe the move that reached each leaf
+                stats.nodes += 1;
+                stats.captures += mv.capture.is_some() as u64;
+                stats.en_passant += mv.en_passant as u64;
+                stats.castles += mv.castling as u64;
+                stats.promotions += mv.promotion.is_some() as u64;
+                if self.board.is_in_check(self.board.turn) {
+                    stats.checks += 1;
+                    stats.checkmates += !self.board.has_legal_move() as u64;
+                }
+            } else {
+                stats.add(&self.perft_stats(depth - 1));
+            }
+
+            self.board.undo_move(mv);
+        }
+
+        stats
+    }
+
+    pub fn perft_parallel(&self, depth: u32, threads: usize) -> u64 {
+        if depth <= 1 {
+            return PerftState::from_board(&self.board).perft(depth);
+        }
+
+        let moves = self.board.generate_legal_moves();
+        let threads = threads.clamp(1, moves.len().max(1));
+
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|worker| {
+                    let moves = &moves;
+                    scope.spawn(move || {
+                        let mut state = PerftState::from_board(&self.board);
+                        let mut nodes = 0;
+                        for mv in moves.iter().skip(worker).step_by(threads) {
+                            state.board.make_move(mv);
+                            nodes += state.perft(depth - 1);
+                            state.board.undo_move(mv);
+                        }
+                        nodes
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap())
+                .sum()
+        })
+    }
+}
@@ -0,0 +1,349 @@
+use crate::board::{Board, Color, FenError, GameOutcome, Move, MoveError};
+use crate::constants::STARTING_POSITION;
+use std::fmt::Display;
+
+const SEVEN_TAG_ROSTER: [(&str, &str); 6] = [
+    ("Event", "?"),
+    ("Site", "?"),
+    ("Date", "????.??.??"),
+    ("Round", "?"),
+    ("White", "?"),
+    ("Black", "?"),
+];
+const MAX_LINE_LENGTH: usize = 80;
+
+pub type Tags = Vec<(String, String)>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PgnError {
+    InvalidTag(String),
+    UnterminatedComment,
+    UnterminatedVariation,
+    InvalidFen(FenError),
+    InvalidMove {
+        token: String,
+        ply: usize,
+        error: MoveError,
+    },
+}
+
+impl Display for PgnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PgnError::InvalidTag(tag) => write!(f, "invalid tag pair '{}'", tag),
+            PgnError::UnterminatedComment => write!(f, "unterminated comment"),
+            PgnError::UnterminatedVariation => write!(f, "unterminated variation"),
+            PgnError::InvalidFen(error) => write!(f, "invalid FEN tag: {}", error),
+            PgnError::InvalidMove { token, ply, error } => {
+                write!(f, "move '{}' at ply {}: {}", token, ply, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PgnError {}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    Ongoing,
+}
+
+impl GameResult {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameResult::WhiteWins => "1-0",
+            GameResult::BlackWins => "0-1",
+            GameResult::Draw => "1/2-1/2",
+            GameResult::Ongoing => "*",
+        }
+    }
+
+    pub fn from_token(token: &str) -> Option<GameResult> {
+        match token {
+            "1-0" => Some(GameResult::WhiteWins),
+            "0-1" => Some(GameResult::BlackWins),
+            "1/2-1/2" => Some(GameResult::Draw),
+            "*" => Some(GameResult::Ongoing),
+            _ => None,
+        }
+    }
+}
+
+impl From<GameOutcome> for GameResult {
+    fn from(outcome: GameOutcome) -> Self {
+        match outcome {
+            GameOutcome::WhiteWins => GameResult::WhiteWins,
+            GameOutcome::BlackWins => GameResult::BlackWins,
+            GameOutcome::Draw(_) => GameResult::Draw,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Game {
+    pub start: Board,
+    pub moves: Vec<Move>,
+    pub result: GameResult,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::new()
+    }
+}
+
+impl Game {
+    pub fn new() -> Self {
+        Game {
+            start: Board::init(),
+            moves: Vec::new(),
+            result: GameResult::Ongoing,
+        }
+    }
+
+    pub fn from_fen(fen: &str) -> Result<Self, FenError> {
+        Ok(Game {
+            start: Board::from_fen(fen)?,
+            moves: Vec::new(),
+            result: GameResult::Ongoing,
+        })
+    }
+
+    pub fn from_board(board: &Board) -> Self {
+        let mut start = board.clone();
+        for mv in board.moves.iter().rev() {
+            start.undo_move(mv);
+        }
+
+        Game {
+            start,
+            moves: board.moves.clone(),
+            result: GameResult::Ongoing,
+        }
+    }
+
+    pub fn push(&mut self, mv: Move) {
+        self.moves.push(mv);
+    }
+
+    pub fn from_pgn(pgn: &str) -> Result<(Game, Tags), PgnError> {
+        let (tags, movetext) = parse_tags(pgn)?;
+
+        let mut game = match tags.iter().find(|(name, _)| name == "FEN") {
+            Some((_, fen)) => Game::from_fen(fen).map_err(PgnError::InvalidFen)?,
+            None => Game::new(),
+        };
+        let mut board = game.start.clone();
+
+        for token in movetext_tokens(movetext)? {
+            if let Some(result) = GameResult::from_token(&token) {
+                game.result = result;
+                break;
+            }
+
+            let mv = board
+                .parse_san(&token)
+                .map_err(|error| PgnError::InvalidMove {
+                    token: token.clone(),
+                    ply: game.moves.len() + 1,
+                    error,
+                })?;
+            board.make_move(&mv);
+            game.push(mv);
+        }
+
+        Ok((game, tags))
+    }
+
+    pub fn to_pgn(&self, tags: &[(String, String)]) -> String {
+        let mut pgn = String::new();
+        let tag_value = |name: &str| {
+            tags.iter()
+                .find(|(tag, _)| tag == name)
+                .map(|(_, value)| value.as_str())
+        };
+
+        for (name, default) in SEVEN_TAG_ROSTER {
+            push_tag(&mut pgn, name, tag_value(name).unwrap_or(default));
+        }
+        push_tag(&mut pgn, "Result", self.result.as_str());
+
+        let start_fen = self.start.to_fen();
+        if start_fen != STARTING_POSITION {
+            push_tag(&mut pgn, "SetUp", "1");
+            push_tag(&mut pgn, "FEN", &start_fen);
+        }
+
+        for (name, value) in tags {
+            let in_roster = SEVEN_TAG_ROSTER.iter().any(|(tag, _)| tag == name);
+            if !in_roster && !["Result", "SetUp", "FEN"].contains(&name.as_str()) {
+                push_tag(&mut pgn, name, value);
+            }
+        }
+        pgn.push('\n');
+
+        let mut board = self.start.clone();
+        let mut tokens = Vec::new();
+        for (index, mv) in self.moves.iter().enumerate() {
+            let move_number = board.ply / 2 + 1;
+            if board.turn == Color::White {
+                tokens.push(format!("{}.", move_number));
+            } else if index == 0 {
+                tokens.push(format!("{}...", move_number));
+            }
+
+            tokens.push(board.to_san(mv));
+            board.make_move(mv);
+        }
+        tokens.push(self.result.as_str().to_string());
+
+        let mut line_length = 0;
+        for token in tokens {
+            if line_length > 0 && line_length + 1 + token.len() > MAX_LINE_LENGTH {
+                pgn.push('\n');
+                line_length = 0;
+            } else if line_length > 0 {
+                pgn.push(' ');
+                line_length += 1;
+            }
+
+            line_length += token.len();
+            pgn.push_str(&token);
+        }
+        pgn.push('\n');
+
+        pgn
+    }
+}
+
+fn push_tag(pgn: &mut String, name: &str, value: &str) {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    pgn.push_str(&format!("[{} \"{}\"]\n", name, escaped));
+}
+
+fn parse_tags(pgn: &str) -> Result<(Tags, &str), PgnError> {
+    let mut tags = Vec::new();
+    let mut rest = pgn.trim_start();
+
+    while rest.starts_with('[') {
+        let mut end = None;
+        let mut in_quotes = false;
+        let mut escaped = false;
+        for (index, c) in rest.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if in_quotes => escaped = true,
+                '"' => in_quotes = !in_quotes,
+                ']' if !in_quotes => {
+                    end = Some(index);
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        let end =
+            end.ok_or_else(|| PgnError::InvalidTag(rest.lines().next().unwrap_or("").to_string()))?;
+        let tag = &rest[1..end];
+        let invalid = || PgnError::InvalidTag(tag.to_string());
+
+        let (name, value) = tag
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or_else(invalid)?;
+        let value = value.trim();
+        if name.is_empty() || value.len() < 2 || !value.starts_with('"') || !value.ends_with('"') {
+            return Err(invalid());
+        }
+
+        let mut unescaped = String::new();
+        let mut chars = value[1..value.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            unescaped.push(if c == '\\' {
+                chars.next().unwrap_or(c)
+            } else {
+                c
+            });
+        }
+        tags.push((name.to_string(), unescaped));
+        rest = rest[end + 1..].trim_start();
+    }
+
+    Ok((tags, rest))
+}
+
+fn movetext_tokens(movetext: &str) -> Result<Vec<String>, PgnError> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut chars = movetext.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                if !chars.by_ref().any(|c| c == '}') {
+                    return Err(PgnError::UnterminatedComment);
+                }
+            }
+            ';' => {
+                chars.by_ref().find(|&c| c == '\n');
+            }
+            '(' => {
+                let mut depth = 1;
+                while depth > 0 {
+                    match chars.next() {
+                        Some('(') => depth += 1,
+                        Some(')') => depth -= 1,
+                        Some('{') => {
+                            if !chars.by_ref().any(|c| c == '}') {
+                                return Err(PgnError::UnterminatedComment);
+                            }
+                        }
+                        Some(_) => {}
+                        None => return Err(PgnError::UnterminatedVariation),
+                    }
+                }
+            }
+            _ if c.is_whitespace() => {}
+            _ => {
+                token.push(c);
+                let at_boundary = chars
+                    .as_str()
+                    .chars()
+                    .next()
+                    .is_none_or(|next| next.is_whitespace() || "{;(".contains(next));
+                if at_boundary {
+                    push_token(&mut tokens, std::mem::take(&mut token));
+                }
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn push_token(tokens: &mut Vec<String>, token: String) {
+    // numeric annotation glyphs carry no move
+    if token.starts_with('$') {
+        return;
+    }
+    if GameResult::from_token(&token).is_some() {
+        tokens.push(token);
+        return;
+    }
+
+    // move numbers may be glued to the move, as in "12...Nf6"
+    let digits = token.len() - token.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let dots = token[digits..].len() - token[digits..].trim_start_matches('.').len();
+    let san = if digits > 0 && dots > 0 {
+        &token[digits + dots..]
+    } else {
+        &token
+    };
+
+    if !san.is_empty() {
+        tokens.push(san.to_string());
+    }
+}
@@ -108,4 +108,47 @@ mod tests {
         let bb: Bitboard = Default::default();
         assert_eq!(bb.value(), 0);
     }
+
+    #[test]
+    fn test_file_fill() {
+        let bb = Bitboard(1 << 27 | 1 << 1);
+
+        assert_eq!(
+            bb.north_fill(),
+            Bitboard(0x0808080808000002 | 0x0202020202020202)
+        );
+        assert_eq!(
+            bb.south_fill(),
+            Bitboard(0x0000000008080808 | 0x0000000000000002)
+        );
+        assert_eq!(
+            bb.file_fill(),
+            Bitboard(0x0808080808080808 | 0x0202020202020202)
+        );
+    }
+
+    #[test]
+    fn test_more_than_one_and_exactly_one() {
+        let empty = Bitboard::new();
+        let single = Bitboard::from_index(63);
+        let multiple = Bitboard(1 << 63 | 1);
+
+        assert!(!empty.more_than_one());
+        assert!(!empty.exactly_one());
+        assert!(!single.more_than_one());
+        assert!(single.exactly_one());
+        assert!(multiple.more_than_one());
+        assert!(!multiple.exactly_one());
+    }
+
+    #[test]
+    fn test_pop_lsb() {
+        let mut bb = Bitboard(1 << 63 | 1 << 27 | 1 << 3);
+
+        assert_eq!(bb.pop_lsb(), Some(3));
+        assert_eq!(bb.pop_lsb(), Some(27));
+        assert_eq!(bb.pop_lsb(), Some(63));
+        assert_eq!(bb.pop_lsb(), None);
+        assert!(bb.is_empty());
+    }
 }
@@ -1,5 +1,7 @@
 use aether::bitboard::Bitboard;
 use aether::board::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 #[cfg(test)]
 mod tests {
@@ -684,7 +686,7 @@ mod tests {
         board.set_fen("k7/8/8/2p5/8/8/P6P/6rK w - - 0 1");
 
         let mut moves = board.generate_king_moves();
-        let mut moves_assert = vec![Move {
+        let mut moves_assert = [Move {
             from: 7,
             to: 6,
             piece: Piece::King,
@@ -798,4 +800,1257 @@ mod tests {
         assert!(!board.pieces[Color::White as usize][Piece::Pawn as usize].is_set(28));
         assert_eq!(fen_before, board.to_fen());
     }
+
+    fn knight_move(from: usize, to: usize, color: Color) -> Move {
+        Move {
+            from,
+            to,
+            piece: Piece::Knight,
+            color,
+            en_passant: false,
+            castling: false,
+            promotion: None,
+            capture: None,
+        }
+    }
+
+    #[test]
+    fn test_threefold_repetition_across_game_moves() {
+        let mut board = Board::init();
+        let shuffle = [
+            knight_move(6, 21, Color::White),
+            knight_move(62, 45, Color::Black),
+            knight_move(21, 6, Color::White),
+            knight_move(45, 62, Color::Black),
+        ];
+
+        assert_eq!(board.repetition_count(), 1);
+
+        for mv in shuffle.iter() {
+            board.make_move(mv);
+        }
+        assert_eq!(board.repetition_count(), 2);
+        assert!(!board.is_threefold_repetition());
+
+        for mv in shuffle.iter() {
+            board.make_move(mv);
+        }
+        assert_eq!(board.repetition_count(), 3);
+        assert!(board.is_threefold_repetition());
+
+        board.undo_move(&shuffle[3]);
+        assert!(!board.is_threefold_repetition());
+    }
+
+    #[test]
+    fn test_repetition_window_resets_after_pawn_move() {
+        let mut board = Board::init();
+        let shuffle = [
+            knight_move(6, 21, Color::White),
+            knight_move(62, 45, Color::Black),
+            knight_move(21, 6, Color::White),
+            knight_move(45, 62, Color::Black),
+        ];
+
+        for mv in shuffle.iter() {
+            board.make_move(mv);
+        }
+
+        let pawn_move = Move {
+            from: 12,
+            to: 20,
+            piece: Piece::Pawn,
+            color: Color::White,
+            en_passant: false,
+            castling: false,
+            promotion: None,
+            capture: None,
+        };
+        board.make_move(&pawn_move);
+
+        assert_eq!(board.repetition_count(), 1);
+    }
+
+    #[test]
+    fn test_last_irreversible_ply() {
+        // the clock claims 40 quiet plies that are not in the history
+        let mut board = Board::from_fen("4k3/8/8/3p4/4P3/8/8/4K1N1 w - - 40 30").unwrap();
+        let loaded_ply = board.last_irreversible_ply();
+        assert_eq!(loaded_ply, board.ply as usize);
+
+        let quiet = board.parse_san("Nf3").unwrap();
+        board.make_move(&quiet);
+        assert_eq!(board.last_irreversible_ply(), loaded_ply);
+
+        let reply = board.parse_san("Kd7").unwrap();
+        board.make_move(&reply);
+        let capture = board.parse_san("exd5").unwrap();
+        board.make_move(&capture);
+        assert_eq!(board.last_irreversible_ply(), board.ply as usize);
+        assert_eq!(board.repetition_count(), 1);
+
+        board.undo_move(&capture);
+        assert_eq!(board.last_irreversible_ply(), loaded_ply);
+    }
+
+    fn direction(from: usize, to: usize) -> Option<(i32, i32)> {
+        let file_delta = (to % 8) as i32 - (from % 8) as i32;
+        let rank_delta = (to / 8) as i32 - (from / 8) as i32;
+
+        if from != to
+            && (file_delta == 0 || rank_delta == 0 || file_delta.abs() == rank_delta.abs())
+        {
+            Some((file_delta.signum(), rank_delta.signum()))
+        } else {
+            None
+        }
+    }
+
+    fn line_between_by_loop(from: usize, to: usize) -> Bitboard {
+        let mut between = Bitboard::new();
+
+        if let Some((file_step, rank_step)) = direction(from, to) {
+            let mut index = from as i32 + rank_step * 8 + file_step;
+            while index != to as i32 {
+                between.set_bit(index as usize);
+                index += rank_step * 8 + file_step;
+            }
+        }
+
+        between
+    }
+
+    fn line_through_by_loop(from: usize, to: usize) -> Bitboard {
+        let mut line = Bitboard::new();
+
+        if let Some((file_step, rank_step)) = direction(from, to) {
+            line.set_bit(from);
+            for square in 0..64 {
+                if let Some(step) = direction(from, square) {
+                    if step == (file_step, rank_step) || step == (-file_step, -rank_step) {
+                        line.set_bit(square);
+                    }
+                }
+            }
+        }
+
+        line
+    }
+
+    #[test]
+    fn test_line_tables_match_loop_computation() {
+        for from in 0..64 {
+            for to in 0..64 {
+                assert_eq!(
+                    Board::line_between(from, to),
+                    line_between_by_loop(from, to),
+                    "line_between({}, {})",
+                    from,
+                    to
+                );
+                assert_eq!(
+                    Board::line_through(from, to),
+                    line_through_by_loop(from, to),
+                    "line_through({}, {})",
+                    from,
+                    to
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_empty_between() {
+        let board = Board::init();
+
+        assert!(!board.is_empty_between(4, 7));
+        assert!(board.is_empty_between(12, 52));
+        assert!(!board.is_empty_between(4, 60));
+    }
+
+    #[test]
+    fn test_board_display_is_fen() {
+        let board = Board::init();
+
+        assert_eq!(format!("{}", board), aether::constants::STARTING_POSITION);
+    }
+
+    #[test]
+    fn test_board_display_from_str_round_trip() {
+        let fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 b - - 12 40",
+        ];
+
+        for fen in fens {
+            let board: Board = fen.parse().unwrap();
+            let reparsed = board.to_string().parse::<Board>().unwrap();
+
+            assert_eq!(reparsed.to_fen(), fen);
+            assert_eq!(reparsed.pieces, board.pieces);
+            assert_eq!(
+                reparsed.game_state.current_zobrist,
+                board.game_state.current_zobrist
+            );
+        }
+    }
+
+    #[test]
+    fn test_board_from_str_errors() {
+        assert_eq!(
+            "8/8/8/8/8/8/8/8 w -".parse::<Board>().err(),
+            Some(FenError::MissingFields(3))
+        );
+        assert_eq!(
+            "8/8/8/8/8/8/8 w - - 0 1".parse::<Board>().err(),
+            Some(FenError::InvalidBoard("8/8/8/8/8/8/8".to_string()))
+        );
+        assert_eq!(
+            "8/8/8/8/8/8/8/7X w - - 0 1".parse::<Board>().err(),
+            Some(FenError::InvalidPiece('X'))
+        );
+        assert_eq!(
+            "8/8/8/8/8/8/8/8 x - - 0 1".parse::<Board>().err(),
+            Some(FenError::InvalidSideToMove("x".to_string()))
+        );
+        assert_eq!(
+            "8/8/8/8/8/8/8/8 w KX - 0 1".parse::<Board>().err(),
+            Some(FenError::InvalidCastlingRights("KX".to_string()))
+        );
+        assert_eq!(
+            "8/8/8/8/8/8/8/8 w - z9 0 1".parse::<Board>().err(),
+            Some(FenError::InvalidEnPassant("z9".to_string()))
+        );
+        assert_eq!(
+            "8/8/8/8/8/8/8/8 w - - x 1".parse::<Board>().err(),
+            Some(FenError::InvalidHalfmoveClock("x".to_string()))
+        );
+        assert_eq!(
+            "8/8/8/8/8/8/8/8 w - - 0 0".parse::<Board>().err(),
+            Some(FenError::InvalidFullmoveNumber("0".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_slider_attacks_stop_at_blockers() {
+        let board = Board::from_fen("8/8/8/8/1p1R2P1/8/8/8 w - - 0 1").unwrap();
+        let rook = Board::square_to_index("d4");
+
+        let attacks = Board::rook_attacks(rook, board.occupied());
+
+        for square in ["b4", "c4", "e4", "f4", "g4", "d1", "d8"] {
+            assert!(attacks.is_set(Board::square_to_index(square)), "{}", square);
+        }
+        for square in ["a4", "h4", "d4"] {
+            assert!(
+                !attacks.is_set(Board::square_to_index(square)),
+                "{}",
+                square
+            );
+        }
+        assert_eq!(attacks.count_bits(), 12);
+    }
+
+    #[test]
+    fn test_is_square_attacked() {
+        let board = Board::from_fen("4k3/8/8/8/8/2n5/8/R3K3 w - - 0 1").unwrap();
+
+        assert!(board.is_square_attacked(Board::square_to_index("e2"), Color::Black));
+        assert!(board.is_square_attacked(Board::square_to_index("a8"), Color::White));
+        assert!(!board.is_square_attacked(Board::square_to_index("h8"), Color::White));
+        assert!(!board.is_square_attacked(Board::square_to_index("f1"), Color::Black));
+    }
+
+    #[test]
+    fn test_attackers_in_king_ring_with_pawn_storm() {
+        let board = Board::from_fen("r5k1/5ppp/8/8/3q4/6pp/5PP1/6K1 w - - 0 1").unwrap();
+        let ring = board.king_ring(Color::White);
+
+        assert_eq!(ring.count_bits(), 6);
+
+        let (count, weight) = board.attackers_in_region(ring, Color::Black);
+        assert_eq!(count, 3);
+        assert_eq!(
+            weight,
+            2 * aether::constants::KING_ATTACK_WEIGHTS[Piece::Pawn as usize]
+                + aether::constants::KING_ATTACK_WEIGHTS[Piece::Queen as usize]
+        );
+
+        let (count, _) = board.attackers_in_region(board.king_ring(Color::Black), Color::White);
+        assert_eq!(count, 0);
+    }
+
+    fn capture(board: &Board, from: &str, to: &str) -> Move {
+        let from = Board::square_to_index(from);
+        let to = Board::square_to_index(to);
+        let moving = board.piece_at(from).unwrap();
+
+        Move {
+            from,
+            to,
+            piece: moving.piece,
+            color: moving.color,
+            en_passant: false,
+            castling: false,
+            promotion: None,
+            capture: board.piece_at(to).map(|captured| captured.piece),
+        }
+    }
+
+    #[test]
+    fn test_see_undefended_pawn() {
+        let board = Board::from_fen("1k1r4/1pp4p/p7/4p3/8/P5P1/1PP4P/2K1R3 w - - 0 1").unwrap();
+
+        assert_eq!(board.see(&capture(&board, "e1", "e5")), 100);
+    }
+
+    #[test]
+    fn test_see_with_x_rays() {
+        let board =
+            Board::from_fen("1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1").unwrap();
+
+        assert_eq!(board.see(&capture(&board, "d3", "e5")), -220);
+    }
+
+    #[test]
+    fn test_order_moves_with_see_breaks_ties_deterministically() {
+        let board = Board::from_fen("4k3/8/8/p6p/8/8/8/R3K2R w - - 0 1").unwrap();
+        let captures = vec![
+            capture(&board, "a1", "a5"),
+            capture(&board, "h1", "h5"),
+            capture(&board, "e1", "e2"),
+        ];
+
+        let mut ordered = captures.clone();
+        board.order_moves_with_see(&mut ordered);
+        let mut reversed: Vec<Move> = captures.iter().rev().copied().collect();
+        board.order_moves_with_see(&mut reversed);
+
+        assert_eq!(board.see(&captures[0]), board.see(&captures[1]));
+        assert_eq!(ordered, reversed);
+        assert_eq!(ordered[0].from, Board::square_to_index("a1"));
+        assert_eq!(ordered[1].from, Board::square_to_index("h1"));
+        assert_eq!(ordered[2].capture, None);
+    }
+
+    #[test]
+    fn test_can_castle_now() {
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        assert!(board.can_castle_now(Color::White, true));
+        assert!(board.can_castle_now(Color::White, false));
+        assert!(board.can_castle_now(Color::Black, true));
+        assert!(board.can_castle_now(Color::Black, false));
+    }
+
+    #[test]
+    fn test_can_castle_now_false_in_check() {
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/4q3/R3K2R w KQkq - 0 1").unwrap();
+
+        assert!(board.is_in_check(Color::White));
+        assert!(board.can_castle(Color::White, true));
+        assert!(!board.can_castle_now(Color::White, true));
+        assert!(!board.can_castle_now(Color::White, false));
+    }
+
+    #[test]
+    fn test_can_castle_now_false_through_attacked_square() {
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let attacked = Board::from_fen("r3k2r/8/8/8/8/8/6p1/R3K2R w KQkq - 0 1").unwrap();
+        let b_file_attacked = Board::from_fen("1r2k2r/8/8/8/8/8/8/R3K2R w KQk - 0 1").unwrap();
+
+        assert!(board.can_castle_now(Color::White, true));
+        assert!(!attacked.can_castle_now(Color::White, true));
+        assert!(attacked.can_castle_now(Color::White, false));
+        // only the king's path has to be safe, b1 may be attacked
+        assert!(b_file_attacked.can_castle_now(Color::White, false));
+    }
+
+    #[test]
+    fn test_chess960_castling_rights_from_fen() {
+        let shredder = Board::from_fen("1r2k1r1/8/8/8/8/8/8/1R2K1R1 w GBgb - 0 1").unwrap();
+        let x_fen = Board::from_fen("1r2k1r1/8/8/8/8/8/8/1R2K1R1 w KQkq - 0 1").unwrap();
+
+        assert!(shredder.chess960);
+        assert_eq!(shredder.castling_rooks, [6, 1, 62, 57]);
+        assert_eq!(x_fen.castling_rooks, shredder.castling_rooks);
+        assert_eq!(
+            shredder.to_fen(),
+            "1r2k1r1/8/8/8/8/8/8/1R2K1R1 w GBgb - 0 1"
+        );
+    }
+
+    #[test]
+    fn test_chess960_castling_rook_does_not_shield_path() {
+        // the b1 rook blocks the a1 rook until it moves to d1
+        let board = Board::from_fen("7k/8/8/8/8/8/8/rRK5 w B - 0 1").unwrap();
+
+        assert!(board.can_castle(Color::White, false));
+        assert!(!board.can_castle_now(Color::White, false));
+    }
+
+    #[test]
+    fn test_set_fen_clears_chess960_flag() {
+        let mut board = Board::from_fen("1r2k1r1/8/8/8/8/8/8/1R2K1R1 w GBgb - 0 1").unwrap();
+        assert!(board.chess960);
+
+        let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        board.set_fen(fen);
+
+        assert!(!board.chess960);
+        assert_eq!(board.castling_rooks, aether::constants::CASTLING_ROOKS);
+        assert_eq!(board.to_fen(), fen);
+    }
+
+    #[test]
+    fn test_chess960_castling_path_ignores_castling_pieces() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/RK6 w A - 0 1").unwrap();
+        let blocked = Board::from_fen("4k3/8/8/8/8/8/8/RK1N4 w A - 0 1").unwrap();
+
+        assert!(board.can_castle_now(Color::White, false));
+        assert!(!blocked.can_castle_now(Color::White, false));
+    }
+
+    #[test]
+    fn test_from_fen_validated() {
+        assert!(Board::from_fen_validated(aether::constants::STARTING_POSITION).is_ok());
+        assert!(matches!(
+            Board::from_fen_validated("4k3/8/8/8/8/8/8/8 w - - 0 1"),
+            Err(FenError::IllegalPosition(_))
+        ));
+        assert!(matches!(
+            Board::from_fen_validated("4k3/8/8/8/8/8/8/4K2q b - - 0 1"),
+            Err(FenError::IllegalPosition(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_fen_validated_rejects_nine_pawns() {
+        let fen = "4k3/8/8/8/8/P7/PPPPPPPP/4K3 w - - 0 1";
+
+        assert!(Board::from_fen(fen).is_ok());
+        assert!(matches!(
+            Board::from_fen_validated(fen),
+            Err(FenError::IllegalPosition(_))
+        ));
+
+        let relaxed = MaterialLimits {
+            max_pawns: 9,
+            ..MaterialLimits::default()
+        };
+        assert!(Board::from_fen(fen)
+            .unwrap()
+            .validate_legal_with(&relaxed)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_parse_san() {
+        let board = Board::init();
+
+        let knight = board.parse_san("Nf3").unwrap();
+        assert_eq!(knight.piece, Piece::Knight);
+        assert_eq!(knight.from, Board::square_to_index("g1"));
+        assert_eq!(knight.to, Board::square_to_index("f3"));
+
+        let pawn = board.parse_san("e4").unwrap();
+        assert_eq!(pawn.from, Board::square_to_index("e2"));
+
+        assert_eq!(
+            board.parse_san("Nf6"),
+            Err(MoveError::IllegalMove("Nf6".to_string()))
+        );
+        assert_eq!(
+            board.parse_san("Zz9"),
+            Err(MoveError::InvalidSan("Zz9".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_san_captures_and_castling() {
+        let board = Board::from_fen("r3k2r/8/8/3p4/4P3/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        let capture = board.parse_san("exd5").unwrap();
+        assert_eq!(capture.capture, Some(Piece::Pawn));
+
+        let short = board.parse_san("O-O").unwrap();
+        assert!(short.castling);
+        assert_eq!(short.to, Board::square_to_index("g1"));
+
+        let long = board.parse_san("O-O-O+").unwrap();
+        assert_eq!(long.to, Board::square_to_index("c1"));
+
+        let en_passant = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        assert!(en_passant.parse_san("exd6").unwrap().en_passant);
+    }
+
+    #[test]
+    fn test_parse_san_rejects_loose_captures() {
+        let board = Board::from_fen("4k3/8/8/3p4/4P3/2N5/8/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(
+            board.parse_san("d5"),
+            Err(MoveError::IllegalMove("d5".to_string()))
+        );
+        assert_eq!(
+            board.parse_san("xd5"),
+            Err(MoveError::InvalidSan("xd5".to_string()))
+        );
+        assert_eq!(
+            board.parse_san("Nd5"),
+            Err(MoveError::IllegalMove("Nd5".to_string()))
+        );
+        assert_eq!(
+            board.parse_san("Nxe2"),
+            Err(MoveError::IllegalMove("Nxe2".to_string()))
+        );
+        assert!(board.parse_san("Nxd5").unwrap().capture.is_some());
+        assert!(board.parse_san("exd5").unwrap().capture.is_some());
+    }
+
+    #[test]
+    fn test_parse_san_disambiguation() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/1N1NK2N w - - 0 1").unwrap();
+
+        assert_eq!(
+            board.parse_san("Nc3"),
+            Err(MoveError::AmbiguousMove("Nc3".to_string()))
+        );
+        assert_eq!(
+            board.parse_san("Nbc3").unwrap().from,
+            Board::square_to_index("b1")
+        );
+
+        let ranks = Board::from_fen("4k3/8/8/N7/8/8/8/N3K3 w - - 0 1").unwrap();
+        assert_eq!(
+            ranks.parse_san("N5b3").unwrap().from,
+            Board::square_to_index("a5")
+        );
+    }
+
+    #[test]
+    fn test_parse_san_promotion() {
+        let board = Board::from_fen("3r1k2/4P3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+
+        let queen = board.parse_san("exd8=Q+").unwrap();
+        assert_eq!(queen.promotion, Some(Piece::Queen));
+        assert_eq!(queen.capture, Some(Piece::Rook));
+
+        assert_eq!(
+            board.parse_san("e8N").unwrap().promotion,
+            Some(Piece::Knight)
+        );
+        assert!(matches!(
+            board.parse_san("e8"),
+            Err(MoveError::IllegalMove(_))
+        ));
+    }
+
+    #[test]
+    fn test_to_san() {
+        let board = Board::from_fen("r3k2r/8/8/3p4/4P3/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        for san in ["exd5", "O-O", "O-O-O", "Ra2", "Kf1"] {
+            assert_eq!(board.to_san(&board.parse_san(san).unwrap()), san);
+        }
+
+        let en_passant = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        assert_eq!(
+            en_passant.to_san(&en_passant.parse_san("exd6").unwrap()),
+            "exd6"
+        );
+    }
+
+    #[test]
+    fn test_to_san_disambiguation() {
+        let files = Board::from_fen("4k3/8/8/8/8/8/8/1N1NK2N w - - 0 1").unwrap();
+        let ranks = Board::from_fen("4k3/8/8/N7/8/8/8/N3K3 w - - 0 1").unwrap();
+        let both = Board::from_fen("4k3/8/8/8/Q1Q5/8/Q7/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(files.to_san(&files.parse_san("Nbc3").unwrap()), "Nbc3");
+        assert_eq!(files.to_san(&files.parse_san("Ne3").unwrap()), "Ne3");
+        assert_eq!(ranks.to_san(&ranks.parse_san("N5b3").unwrap()), "N5b3");
+        assert_eq!(both.to_san(&both.parse_san("Qa4b3").unwrap()), "Qa4b3");
+    }
+
+    #[test]
+    fn test_to_san_promotion_check_and_mate() {
+        let promotion = Board::from_fen("3r1k2/4P3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let mate = Board::from_fen("6k1/5ppp/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+
+        assert_eq!(
+            promotion.to_san(&promotion.parse_san("exd8=Q").unwrap()),
+            "exd8=Q+"
+        );
+        assert_eq!(mate.to_san(&mate.parse_san("Ra8").unwrap()), "Ra8#");
+    }
+
+    #[test]
+    fn test_invariants_hold_through_random_games() {
+        let mut rng = StdRng::seed_from_u64(1755);
+
+        for fen in [
+            aether::constants::STARTING_POSITION,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        ] {
+            for _ in 0..20 {
+                let mut board = Board::from_fen(fen).unwrap();
+                let mut played = Vec::new();
+
+                for _ in 0..80 {
+                    let moves = board.generate_legal_moves();
+                    if moves.is_empty() {
+                        break;
+                    }
+
+                    let mv = moves[rng.random_range(0..moves.len())];
+                    board.make_move(&mv);
+                    played.push(mv);
+                    assert!(board.check_invariants());
+
+                    if rng.random_range(0..4) == 0 {
+                        let mv = played.pop().unwrap();
+                        board.undo_move(&mv);
+                        assert!(board.check_invariants());
+                    }
+                }
+
+                while let Some(mv) = played.pop() {
+                    board.undo_move(&mv);
+                    assert!(board.check_invariants());
+                }
+                assert_eq!(board.to_fen(), Board::from_fen(fen).unwrap().to_fen());
+            }
+        }
+    }
+
+    #[test]
+    fn test_legal_moves_with_hashes_match_make_move() {
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1",
+        ] {
+            let mut board = Board::from_fen(fen).unwrap();
+
+            for (mv, hash) in board.legal_moves_with_hashes() {
+                board.make_move(&mv);
+                assert_eq!(board.game_state.current_zobrist, hash);
+                // make_move derives its key from hash_after, so also compare
+                // against a from-scratch hash
+                assert!(board.check_invariants());
+                board.undo_move(&mv);
+            }
+        }
+    }
+
+    #[test]
+    fn test_square_distances() {
+        let a1 = Board::square_to_index("a1");
+        let h8 = Board::square_to_index("h8");
+        let e4 = Board::square_to_index("e4");
+        let f6 = Board::square_to_index("f6");
+
+        assert_eq!(Board::chebyshev_distance(a1, h8), 7);
+        assert_eq!(Board::manhattan_distance(a1, h8), 14);
+        assert_eq!(Board::chebyshev_distance(e4, f6), 2);
+        assert_eq!(Board::manhattan_distance(f6, e4), 3);
+        assert_eq!(Board::chebyshev_distance(e4, e4), 0);
+    }
+
+    #[test]
+    fn test_has_legal_move() {
+        let checkmate =
+            Board::from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3")
+                .unwrap();
+        let stalemate = Board::from_fen("k7/8/1Q6/8/8/8/8/4K3 b - - 0 1").unwrap();
+        let only_pawn_move = Board::from_fen("k7/8/1Q6/8/8/8/p7/4K3 b - - 0 1").unwrap();
+
+        assert!(Board::init().has_legal_move());
+        assert!(!checkmate.has_legal_move());
+        assert!(!stalemate.has_legal_move());
+        assert!(only_pawn_move.has_legal_move());
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            assert_eq!(
+                board.has_legal_move(),
+                !board.generate_legal_moves().is_empty()
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_quiet() {
+        let start = Board::init();
+        let hanging_queen =
+            Board::from_fen("rnb1kbnr/pppp1ppp/8/4q3/3P4/8/PPP2PPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        let own_queen_en_prise =
+            Board::from_fen("rnbqkbnr/ppp1pppp/8/3p4/4Q3/8/PPPP1PPP/RNB1KBNR w KQkq - 0 1")
+                .unwrap();
+        let in_check =
+            Board::from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3")
+                .unwrap();
+        let defended_exchange =
+            Board::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3")
+                .unwrap();
+
+        assert!(start.is_quiet());
+        assert!(!hanging_queen.is_quiet());
+        assert!(!own_queen_en_prise.is_quiet());
+        assert!(!in_check.is_quiet());
+        assert!(defended_exchange.is_quiet());
+    }
+
+    #[test]
+    fn test_king_square_cache() {
+        let mut board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        assert_eq!(board.king_square(Color::White), Some(4));
+        assert_eq!(board.king_square(Color::Black), Some(60));
+
+        let king_move = board.parse_san("Kd2").unwrap();
+        board.make_move(&king_move);
+        assert_eq!(board.king_square(Color::White), Some(11));
+        board.undo_move(&king_move);
+        assert_eq!(board.king_square(Color::White), Some(4));
+
+        let castle = board.parse_san("O-O").unwrap();
+        board.make_move(&castle);
+        assert_eq!(board.king_square(Color::White), Some(6));
+
+        let long_castle = board.parse_san("O-O-O").unwrap();
+        board.make_move(&long_castle);
+        assert_eq!(board.king_square(Color::Black), Some(58));
+
+        board.undo_move(&long_castle);
+        board.undo_move(&castle);
+        assert_eq!(board.king_square(Color::White), Some(4));
+        assert_eq!(board.king_square(Color::Black), Some(60));
+    }
+
+    #[test]
+    fn test_parse_uci_move() {
+        let board = Board::from_fen("4k3/P7/8/8/8/8/8/R3K2R w KQ - 0 1").unwrap();
+
+        let mv = board.parse_uci_move("a7a8n").unwrap();
+        assert_eq!((mv.from, mv.to), (48, 56));
+        assert_eq!(mv.promotion, Some(Piece::Knight));
+
+        let castle = board.parse_uci_move("e1g1").unwrap();
+        assert!(castle.castling);
+        assert_eq!(board.parse_uci_move("e1h1"), Ok(castle));
+    }
+
+    #[test]
+    fn test_parse_uci_move_errors() {
+        let board = Board::from_fen("4k3/P7/8/8/8/8/8/R3K2R w KQ - 0 1").unwrap();
+
+        assert_eq!(
+            board.parse_uci_move("i1a2"),
+            Err(MoveParseError::InvalidSquare("i1".to_string()))
+        );
+        assert_eq!(
+            board.parse_uci_move("a1"),
+            Err(MoveParseError::InvalidFormat("a1".to_string()))
+        );
+        assert_eq!(
+            board.parse_uci_move("e1e2x"),
+            Err(MoveParseError::InvalidFormat("e1e2x".to_string()))
+        );
+        assert_eq!(
+            board.parse_uci_move("a7a8qq"),
+            Err(MoveParseError::InvalidFormat("a7a8qq".to_string()))
+        );
+        assert_eq!(
+            board.parse_uci_move("a7a8k"),
+            Err(MoveParseError::InvalidPromotion('k'))
+        );
+        assert_eq!(
+            board.parse_uci_move("b2b4"),
+            Err(MoveParseError::NotOnBoard("b2".to_string()))
+        );
+        assert_eq!(
+            board.parse_uci_move("e8e7"),
+            Err(MoveParseError::NotOnBoard("e8".to_string()))
+        );
+        assert_eq!(
+            board.parse_uci_move("a1b2"),
+            Err(MoveParseError::IllegalMove("a1b2".to_string()))
+        );
+        assert_eq!(
+            board.parse_uci_move("a7a8"),
+            Err(MoveParseError::IllegalMove("a7a8".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_is_legal_matches_generator() {
+        for fen in [
+            aether::constants::STARTING_POSITION,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            let legal = board.generate_legal_moves();
+
+            for mv in board.generate_pseudo_legal_moves() {
+                assert_eq!(
+                    board.is_legal(&mv),
+                    legal.contains(&mv),
+                    "{:?} in {}",
+                    mv,
+                    fen
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_legal_rejects_pinned_piece() {
+        let board = Board::from_fen("4k3/4r3/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+        let mv = board.parse_san("Nf4");
+
+        assert!(mv.is_err());
+        let pinned = Move {
+            from: 12,
+            to: 29,
+            piece: Piece::Knight,
+            color: Color::White,
+            en_passant: false,
+            castling: false,
+            promotion: None,
+            capture: None,
+        };
+        assert!(!board.is_legal(&pinned));
+    }
+
+    #[test]
+    fn test_is_legal_rejects_castling_through_check() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/5r2/R3K2R w KQ - 0 1").unwrap();
+
+        assert_eq!(
+            board.parse_uci_move("e1g1"),
+            Err(MoveParseError::IllegalMove("e1g1".to_string()))
+        );
+        assert!(board.parse_uci_move("e1c1").unwrap().castling);
+    }
+
+    #[test]
+    fn test_is_legal_rejects_chess960_castle_into_check() {
+        let board = Board::from_fen("7k/8/8/8/8/8/8/rRK5 w B - 0 1").unwrap();
+        let castle = Move {
+            from: 2,
+            to: 2,
+            piece: Piece::King,
+            color: Color::White,
+            en_passant: false,
+            castling: true,
+            promotion: None,
+            capture: None,
+        };
+
+        assert!(!board.legal_moves().contains(&castle));
+        assert!(!board.is_legal(&castle));
+        assert!(!board.is_legal(&Move {
+            en_passant: true,
+            ..castle
+        }));
+        assert_eq!(
+            board.parse_uci_move("c1b1"),
+            Err(MoveParseError::IllegalMove("c1b1".to_string()))
+        );
+
+        let legal = board.legal_moves();
+        for mv in board.generate_pseudo_legal_moves() {
+            assert_eq!(board.is_legal(&mv), legal.contains(&mv), "{:?}", mv);
+        }
+    }
+
+    #[test]
+    fn test_is_legal_rejects_en_passant_discovering_check() {
+        let board = Board::from_fen("8/8/8/K2pP2r/8/8/8/4k3 w - d6 0 1").unwrap();
+        let en_passant = Move {
+            from: 36,
+            to: 43,
+            piece: Piece::Pawn,
+            color: Color::White,
+            en_passant: true,
+            castling: false,
+            promotion: None,
+            capture: Some(Piece::Pawn),
+        };
+
+        assert!(!board.is_legal(&en_passant));
+        assert_eq!(
+            board.parse_uci_move("e5d6"),
+            Err(MoveParseError::IllegalMove("e5d6".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_make_move_checked_rejects_friendly_capture() {
+        let mut board = Board::init();
+        let fen = board.to_fen();
+        let malformed = Move {
+            from: 6,
+            to: 12,
+            piece: Piece::Knight,
+            color: Color::White,
+            en_passant: false,
+            castling: false,
+            promotion: None,
+            capture: None,
+        };
+
+        assert_eq!(
+            board.make_move_checked(&malformed),
+            Err(MoveError::OccupiedByFriendly("e2".to_string()))
+        );
+        assert_eq!(board.to_fen(), fen);
+
+        let mv = board.parse_san("Nf3").unwrap();
+        assert_eq!(board.make_move_checked(&mv), Ok(()));
+        assert_eq!(board.king_square(Color::White), Some(4));
+    }
+
+    #[test]
+    fn test_make_move_checked_allows_chess960_castle_onto_rook() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/5KR1 w G - 0 1").unwrap();
+        let castle = board.parse_uci_move("f1g1").unwrap();
+
+        assert!(castle.castling);
+        assert_eq!(board.make_move_checked(&castle), Ok(()));
+        assert_eq!(board.king_square(Color::White), Some(6));
+    }
+
+    #[test]
+    fn test_castling_rights_hash_independent_of_move_order() {
+        let fen = "rn2k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        let play = |moves: &[&str]| {
+            let mut board = Board::from_fen(fen).unwrap();
+            for san in moves {
+                let mv = board.parse_san(san).unwrap();
+                board.make_move(&mv);
+            }
+            board
+        };
+
+        let king_walk = play(&["Ke2", "Nc6", "Ke1", "Nb8"]);
+        let rook_walks = play(&["Rb1", "Nc6", "Ra1", "Nb8", "Rg1", "Nc6", "Rh1", "Nb8"]);
+        let loaded = Board::from_fen("rn2k2r/8/8/8/8/8/8/R3K2R w kq - 0 1").unwrap();
+
+        assert_eq!(
+            king_walk.game_state.castling_rights,
+            aether::constants::CASTLING_RIGHTS[1]
+        );
+        assert_eq!(
+            rook_walks.game_state.castling_rights,
+            aether::constants::CASTLING_RIGHTS[1]
+        );
+        assert_eq!(
+            king_walk.game_state.current_zobrist,
+            rook_walks.game_state.current_zobrist
+        );
+        assert_eq!(
+            king_walk.game_state.current_zobrist,
+            loaded.game_state.current_zobrist
+        );
+    }
+
+    #[test]
+    fn test_checkmate_and_stalemate() {
+        let back_rank = Board::from_fen("3R2k1/5ppp/8/8/8/8/8/6K1 b - - 0 1").unwrap();
+        assert!(back_rank.is_checkmate());
+        assert!(!back_rank.is_stalemate());
+        assert_eq!(back_rank.game_result(), Some(GameOutcome::WhiteWins));
+
+        let stalemate = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+        assert!(stalemate.is_stalemate());
+        assert!(!stalemate.is_checkmate());
+        assert_eq!(stalemate.is_draw(), Some(DrawReason::Stalemate));
+        assert_eq!(
+            stalemate.game_result(),
+            Some(GameOutcome::Draw(DrawReason::Stalemate))
+        );
+
+        assert_eq!(Board::init().game_result(), None);
+    }
+
+    #[test]
+    fn test_is_draw_reasons() {
+        let draw = |fen: &str| Board::from_fen(fen).unwrap().is_draw();
+
+        assert_eq!(
+            draw("4k3/8/8/8/8/8/4P3/4K3 w - - 100 80"),
+            Some(DrawReason::FiftyMoves)
+        );
+        assert_eq!(
+            draw("4k3/8/8/8/8/8/8/4KN2 w - - 0 1"),
+            Some(DrawReason::InsufficientMaterial)
+        );
+        assert_eq!(
+            draw("4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1"),
+            Some(DrawReason::InsufficientMaterial)
+        );
+        assert_eq!(draw("4k1b1/8/8/8/8/8/8/2B1K3 w - - 0 1"), None);
+        assert_eq!(draw("4k3/4p3/8/8/8/8/8/3NKN2 w - - 0 1"), None);
+
+        // mate delivered on the hundredth ply still wins
+        assert_eq!(draw("R5k1/5ppp/8/8/8/8/8/6K1 b - - 100 80"), None);
+        assert_eq!(
+            draw("7k/5Q2/6K1/8/8/8/8/8 b - - 100 80"),
+            Some(DrawReason::Stalemate)
+        );
+
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K1N1 w - - 0 1").unwrap();
+        for san in ["Nf3", "Kd7", "Ng1", "Ke8", "Nf3", "Kd7", "Ng1", "Ke8"] {
+            assert_eq!(board.is_draw(), None);
+            let mv = board.parse_san(san).unwrap();
+            board.make_move(&mv);
+        }
+        assert_eq!(board.is_draw(), Some(DrawReason::Repetition));
+        assert_eq!(
+            board.game_result(),
+            Some(GameOutcome::Draw(DrawReason::Repetition))
+        );
+    }
+
+    #[test]
+    fn test_null_move_hash_toggles_side_and_clears_en_passant() {
+        let mut board =
+            Board::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 3")
+                .unwrap();
+        let fen = board.to_fen();
+        let hash = board.game_state.current_zobrist;
+
+        let expected = board.hash_after_null();
+        board.make_null_move();
+
+        assert_eq!(board.turn, Color::White);
+        assert_eq!(board.game_state.en_passant_square, None);
+        assert_eq!(board.game_state.current_zobrist, expected);
+        let reparsed = Board::from_fen(&board.to_fen()).unwrap();
+        assert_eq!(
+            board.game_state.current_zobrist,
+            reparsed.game_state.current_zobrist
+        );
+
+        board.make_null_move();
+        assert_ne!(board.game_state.current_zobrist, hash);
+        board.undo_null_move();
+        board.undo_null_move();
+
+        assert_eq!(board.to_fen(), fen);
+        assert_eq!(board.game_state.current_zobrist, hash);
+    }
+
+    #[test]
+    fn test_null_move_round_trip_with_real_moves() {
+        let mut board = Board::init();
+        let hash = board.game_state.current_zobrist;
+
+        let e4 = board.parse_san("e4").unwrap();
+        board.make_move(&e4);
+        board.make_null_move();
+        let d4 = board.parse_san("d4").unwrap();
+        board.make_move(&d4);
+
+        // same placement as after 1. e4 and 2. d4 with black to move
+        let direct =
+            Board::from_fen("rnbqkbnr/pppppppp/8/8/3PP3/8/PPP2PPP/RNBQKBNR b KQkq d3 0 2").unwrap();
+        assert_eq!(
+            board.game_state.current_zobrist,
+            direct.game_state.current_zobrist
+        );
+
+        board.undo_move(&d4);
+        board.undo_null_move();
+        board.undo_move(&e4);
+        assert_eq!(board.game_state.current_zobrist, hash);
+        assert_eq!(board.to_fen(), Board::init().to_fen());
+    }
+
+    #[test]
+    fn test_apply_san_line() {
+        let mut board = Board::init();
+        let moves = board
+            .apply_san_line("1. e4 e5 2. Nf3 Nc6 3. Bb5 *")
+            .unwrap();
+
+        assert_eq!(moves.len(), 5);
+        assert_eq!(
+            board.to_fen(),
+            "r1bqkbnr/pppp1ppp/2n5/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3"
+        );
+
+        let mut board = Board::init();
+        assert_eq!(
+            board.apply_san_line("1.e4 e5 2.Ke3"),
+            Err(MoveError::IllegalMove("Ke3".to_string()))
+        );
+        assert_eq!(board.to_fen(), aether::constants::STARTING_POSITION);
+    }
+
+    #[test]
+    fn test_apply_san_line_with_zero_castling() {
+        let mut board = Board::init();
+        let moves = board
+            .apply_san_line(
+                "1. e4 e5 2. Nf3 Nc6 3. Bc4 Bc5 4. 0-0 d6 5.d3 Bg4 6.Nc3 Qd7 7.Be3 0-0-0",
+            )
+            .unwrap();
+
+        assert!(moves[6].castling);
+        assert!(moves[13].castling);
+        assert_eq!(board.king_square(Color::White), Some(6));
+        assert_eq!(board.king_square(Color::Black), Some(58));
+    }
+
+    #[test]
+    fn test_insufficient_material_cases() {
+        let insufficient = |fen: &str| Board::from_fen(fen).unwrap().is_insufficient_material();
+
+        assert!(insufficient("4k3/8/8/8/8/8/8/4K3 w - - 0 1"));
+        assert!(insufficient("4k3/8/8/8/8/8/8/3NKN2 w - - 0 1"));
+        assert!(insufficient("1n2k1n1/8/8/8/8/8/8/4K3 w - - 0 1"));
+        assert!(insufficient("4k3/8/8/8/8/8/1B6/2B1K3 w - - 0 1"));
+        assert!(insufficient("4kb2/8/8/8/8/8/8/B1B1K3 w - - 0 1"));
+        assert!(!insufficient("4k3/8/8/8/8/8/8/2B1KB1B w - - 0 1"));
+        assert!(!insufficient("b3k3/8/8/8/8/8/8/2B1K3 w - - 0 1"));
+
+        assert!(!insufficient("4k3/4p3/8/8/8/8/8/3NKN2 w - - 0 1"));
+        assert!(!insufficient("4k3/8/8/8/8/8/8/3NK1n1 w - - 0 1"));
+        assert!(!insufficient("4k3/8/8/8/8/8/8/3RK3 w - - 0 1"));
+        assert!(!insufficient("4k3/8/8/8/8/8/8/3QK3 w - - 0 1"));
+    }
+
+    #[test]
+    fn test_material_counts() {
+        let count = |board: &Board| {
+            let mut counts = [[0u8; 6]; 2];
+            for square in 0..64 {
+                if let Some(piece_at) = board.piece_at(square) {
+                    counts[piece_at.color as usize][piece_at.piece as usize] += 1;
+                }
+            }
+            counts
+        };
+
+        let mut board = Board::from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let before = board.material_counts();
+        assert_eq!(before, count(&board));
+
+        let capture_promotion = board.parse_san("axb8=Q").unwrap();
+        board.make_move(&capture_promotion);
+        let after = board.material_counts();
+        assert_eq!(after, count(&board));
+        assert_eq!(after[Color::White as usize][Piece::Pawn as usize], 0);
+        assert_eq!(after[Color::White as usize][Piece::Queen as usize], 1);
+        assert_eq!(after[Color::Black as usize][Piece::Knight as usize], 0);
+
+        board.undo_move(&capture_promotion);
+        assert_eq!(board.material_counts(), before);
+    }
+
+    #[test]
+    fn test_threefold_repetition_in_long_game() {
+        let mut board = Board::init();
+        board
+            .apply_san_line(
+                "1. e4 e5 2. Nf3 Nc6 3. Bc4 Bc5 4. c3 Nf6 5. d3 d6 6. O-O O-O \
+                 7. Re1 a6 8. a4 Ba7 9. h3 h6 10. Nbd2 Re8",
+            )
+            .unwrap();
+
+        // the position after 10...Re8 comes back twice more
+        board
+            .apply_san_line("11. Nf1 Rf8 12. N1d2 Re8 13. Nf1 Rf8 14. N1d2 Re8")
+            .unwrap();
+        assert_eq!(board.repetition_count(), 3);
+        assert!(board.is_threefold_repetition());
+
+        // after an irreversible move the earlier occurrences no longer count
+        board.apply_san_line("15. b4 Rf8").unwrap();
+        assert_eq!(board.repetition_count(), 1);
+        board.apply_san_line("16. Nf1 Re8 17. N1d2 Rf8").unwrap();
+        assert_eq!(board.repetition_count(), 2);
+        board
+            .apply_san_line("18. Nf1 Re8 19. N1d2 Rf8 20. Nf1 Re8 21. N1d2 Rf8")
+            .unwrap();
+        assert!(board.is_threefold_repetition());
+        assert!(board.ply > 40);
+    }
+
+    #[test]
+    fn test_square_constants() {
+        use aether::constants::{A1, C6, E2, E4, H8};
+
+        assert_eq!(Board::square_from_file_rank(4, 3), E4);
+        assert_eq!(Board::square_from_file_rank(0, 0), A1);
+        assert_eq!(Board::square_from_file_rank(7, 7), H8);
+        assert_eq!(Board::parse_square("e2"), Some(E2));
+        assert_eq!(Board::index_to_square(C6), "c6");
+
+        for rank in 0..8 {
+            for file in 0..8 {
+                let square = Board::square_from_file_rank(file, rank);
+                let name = Board::index_to_square(square);
+                assert_eq!(Board::parse_square(&name), Some(square));
+            }
+        }
+    }
+
+    #[test]
+    fn test_piece_char_conversion() {
+        assert_eq!(Piece::from_char('n'), Some(Piece::Knight));
+        assert_eq!(Piece::from_char('Q'), Some(Piece::Queen));
+        assert_eq!(Piece::from_char('x'), None);
+
+        for (index, piece) in Piece::all().into_iter().enumerate() {
+            assert_eq!(piece as usize, index);
+            assert_eq!(Piece::from_char(piece.as_char()), Some(piece));
+            assert_eq!(piece.to_string(), piece.as_char().to_string());
+        }
+    }
+
+    #[test]
+    fn test_game_phase() {
+        assert_eq!(Board::init().game_phase(), 24);
+        assert_eq!(
+            Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1")
+                .unwrap()
+                .game_phase(),
+            0
+        );
+        assert_eq!(
+            Board::from_fen("3rk3/pppppppp/8/8/8/8/PPPPPPPP/2B1K1N1 w - - 0 1")
+                .unwrap()
+                .game_phase(),
+            4
+        );
+        // extra queens from promotion do not push past the opening phase
+        assert_eq!(
+            Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/1PPPPPPP/QQBQKBNR w KQkq - 0 1")
+                .unwrap()
+                .game_phase(),
+            24
+        );
+    }
+
+    #[test]
+    fn test_from_fen_without_clocks() {
+        let board =
+            Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -").unwrap();
+        assert_eq!(board.to_fen(), aether::constants::STARTING_POSITION);
+        assert_eq!(board.game_state.fifty_move_ply_count, 0);
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 b - - 12").unwrap();
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/4K3 b - - 12 1");
+    }
 }
@@ -0,0 +1,79 @@
+use aether::board::*;
+use aether::epd::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_epd_operations() {
+        let epd = Epd::parse(
+            "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\";",
+        )
+        .unwrap();
+
+        assert_eq!(epd.id(), Some("WAC.001"));
+        assert_eq!(epd.best_moves.len(), 1);
+        assert_eq!(epd.best_moves[0].piece, Piece::Queen);
+        assert_eq!(epd.best_moves[0].to, Board::square_to_index("g6"));
+        assert!(epd.avoid_moves.is_empty());
+        assert_eq!(
+            epd.board.to_fen(),
+            "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1"
+        );
+    }
+
+    #[test]
+    fn test_parse_epd_multiple_moves_and_quoted_spaces() {
+        let epd = Epd::parse(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - bm e4 d4; am f3; c0 \"opening; main line\"; hmvc 3; fmvn 12",
+        )
+        .unwrap();
+
+        assert_eq!(epd.best_moves.len(), 2);
+        assert_eq!(epd.avoid_moves.len(), 1);
+        assert_eq!(
+            epd.operations.get("c0").map(String::as_str),
+            Some("opening; main line")
+        );
+        assert!(epd.board.to_fen().ends_with(" 3 12"));
+    }
+
+    #[test]
+    fn test_parse_epd_with_move_clocks() {
+        let epd =
+            Epd::parse("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 bm e4;").unwrap();
+
+        assert_eq!(epd.best_moves.len(), 1);
+        assert_eq!(epd.best_moves[0].to, Board::square_to_index("e4"));
+        assert!(!epd.operations.contains_key("0"));
+        assert_eq!(epd.board.to_fen(), aether::constants::STARTING_POSITION);
+
+        let epd = Epd::parse("4k3/8/8/8/8/8/8/4K3 b - - 7 40").unwrap();
+        assert!(epd.board.to_fen().ends_with(" 7 40"));
+    }
+
+    #[test]
+    fn test_parse_epd_errors() {
+        assert_eq!(
+            Epd::parse("8/8/8/8 w").err(),
+            Some(EpdError::MissingFields(2))
+        );
+        assert!(matches!(
+            Epd::parse("4k3/8/8/8/8/8/8/4K3 w - - bm Qh5;"),
+            Err(EpdError::InvalidMove(_))
+        ));
+        assert!(matches!(
+            Epd::parse("4k3/8/8/8/8/8/8/4K3 w - - id \"open;"),
+            Err(EpdError::InvalidOperation(_))
+        ));
+        assert_eq!(
+            Epd::parse("4k3/8/8/8/8/8/8/4K3 w - - 0 bm Kd2;").err(),
+            Some(EpdError::InvalidOperation("0 bm Kd2".to_string()))
+        );
+        assert!(matches!(
+            Epd::parse("4k3/8/8/8/8/8/8/4K3 x - - id \"a\";"),
+            Err(EpdError::InvalidFen(_))
+        ));
+    }
+}
@@ -0,0 +1,258 @@
+use aether::board::*;
+use aether::eval::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(fen: &str) -> Score {
+        let mut board = Board::new();
+        board.set_fen(fen);
+        SimpleEvaluator::new().evaluate(&board)
+    }
+
+    #[test]
+    fn test_starting_position_is_balanced() {
+        assert_eq!(evaluate(aether::constants::STARTING_POSITION), 0);
+    }
+
+    #[test]
+    fn test_evaluation_is_side_to_move_relative() {
+        let white = evaluate("4k3/8/8/8/8/8/8/3QK3 w - - 0 1");
+        let black = evaluate("4k3/8/8/8/8/8/8/3QK3 b - - 0 1");
+
+        assert!(white > 0);
+        assert_eq!(white, -black);
+    }
+
+    #[test]
+    fn test_centralized_knight_beats_rim_knight() {
+        let centre = evaluate("4k3/8/8/8/4N3/8/8/4K3 w - - 0 1");
+        let rim = evaluate("4k3/8/8/8/7N/8/8/4K3 w - - 0 1");
+
+        assert!(centre > rim);
+    }
+
+    #[test]
+    fn test_piece_square_tables_are_tapered() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let phase = board.game_phase() as i32;
+
+        assert_eq!(phase, 0);
+        assert_eq!(
+            SimpleEvaluator::taper(pst::KING_MG[60], pst::KING_EG[60], phase),
+            pst::KING_EG[60]
+        );
+        assert_eq!(evaluate("4k3/8/8/8/8/8/8/4K3 w - - 0 1"), 0);
+    }
+
+    #[test]
+    fn test_open_bishop_beats_hemmed_in_bishop() {
+        // a1 and h1 share a piece-square value, only the b2 pawn differs
+        let open = evaluate("4k3/8/8/8/8/8/1P6/4K2B w - - 0 1");
+        let hemmed_in = evaluate("4k3/8/8/8/8/8/1P6/B3K3 w - - 0 1");
+
+        assert!(open > hemmed_in);
+    }
+
+    #[test]
+    fn test_passed_pawn_bonus() {
+        let advanced_passer = evaluate("4k3/7p/3P4/8/8/8/8/4K3 w - - 0 1");
+        let blocked = evaluate("4k3/3p4/8/3P4/8/8/8/4K3 w - - 0 1");
+
+        assert!(advanced_passer > 50);
+        assert!(advanced_passer > blocked);
+    }
+
+    #[test]
+    fn test_doubled_and_isolated_pawns_penalized() {
+        let healthy = evaluate("4k3/pp6/8/8/8/8/PP6/4K3 w - - 0 1");
+        let doubled = evaluate("4k3/pp6/8/8/8/P7/P7/4K3 w - - 0 1");
+        let isolated = evaluate("4k3/pp6/8/8/8/8/P1P5/4K3 w - - 0 1");
+
+        assert!(healthy > doubled);
+        assert!(healthy > isolated);
+    }
+
+    #[test]
+    fn test_castled_king_with_pawn_shield_is_safer() {
+        let safe = evaluate("r2q1rk1/5ppp/8/8/8/8/5PPP/R2Q1RK1 w - - 0 1");
+        let shattered = evaluate("r2q1rk1/5ppp/8/8/5PPP/8/8/R2Q1RK1 w - - 0 1");
+        let open_file = evaluate("r2q1rk1/5ppp/8/8/8/8/5P1P/R2Q1RK1 w - - 0 1");
+
+        assert!(safe > shattered);
+        assert!(safe > open_file + PIECE_VALUES[Piece::Pawn as usize]);
+    }
+
+    #[test]
+    fn test_bishop_pair_bonus() {
+        let bishop_pair = evaluate("4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1");
+        let bishop_and_knight = evaluate("4k3/8/8/8/8/8/8/2N1KB2 w - - 0 1");
+
+        assert_eq!(
+            PIECE_VALUES[Piece::Bishop as usize],
+            PIECE_VALUES[Piece::Knight as usize]
+        );
+        assert!(bishop_pair > bishop_and_knight);
+    }
+
+    #[test]
+    fn test_bishop_pair_bonus_grows_in_endgame() {
+        let endgame = evaluate("4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1")
+            - evaluate("4k3/8/8/8/8/8/8/2N1KB2 w - - 0 1");
+        let middlegame = evaluate("rn1qk1nr/8/8/8/8/8/8/RNBQKB1R w - - 0 1")
+            - evaluate("rn1qk1nr/8/8/8/8/8/8/RNNQKB1R w - - 0 1");
+
+        assert!(endgame > middlegame);
+        assert!(middlegame > 0);
+    }
+
+    #[test]
+    fn test_rook_open_file_bonus() {
+        let open = evaluate("6k1/ppp5/8/8/8/8/PPP5/3R2K1 w - - 0 1");
+        let semi_open = evaluate("6k1/1ppp4/8/8/8/8/PPP5/3R2K1 w - - 0 1");
+        let closed = evaluate("6k1/1ppp4/8/8/8/8/1PPP4/3R2K1 w - - 0 1");
+
+        assert!(open > semi_open);
+        assert!(semi_open > closed);
+    }
+
+    #[test]
+    fn test_rook_seventh_rank_bonus() {
+        let seventh = evaluate("6k1/Rpp3pp/8/8/8/8/6PP/6K1 w - - 0 1");
+        let first = evaluate("6k1/1pp3pp/8/8/8/8/6PP/R5K1 w - - 0 1");
+
+        assert!(seventh > first);
+    }
+
+    #[test]
+    fn test_winning_king_approaches_lone_king() {
+        let close = evaluate("8/8/8/3k4/8/3K4/8/Q7 w - - 0 1");
+        let far = evaluate("8/8/8/3k4/8/8/8/Q6K w - - 0 1");
+
+        assert!(close > far);
+    }
+
+    #[test]
+    fn test_king_pawn_proximity_in_endgame() {
+        let near_pawns = evaluate("4k3/8/8/8/4P3/4K3/8/8 w - - 0 1");
+        let far_from_pawns = evaluate("4k3/8/8/8/4P3/8/8/K7 w - - 0 1");
+
+        assert!(near_pawns > far_from_pawns);
+    }
+
+    #[test]
+    fn test_contempt_tapers_with_move_number() {
+        let contempt = 30;
+
+        assert_eq!(tapered_contempt(contempt, 1), contempt);
+        assert!(tapered_contempt(contempt, 5) > tapered_contempt(contempt, 60));
+        assert_eq!(tapered_contempt(contempt, CONTEMPT_TAPER_MOVES + 1), 0);
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 60").unwrap();
+        assert_eq!(
+            draw_score(&board, contempt),
+            -tapered_contempt(contempt, 60)
+        );
+    }
+
+    #[test]
+    fn test_pawnless_small_edge_scaled_toward_draw() {
+        let rook_vs_bishop = evaluate("4k3/8/8/3b4/8/8/8/R3K3 w - - 0 1");
+        let with_pawn = evaluate("4k3/8/8/3b4/8/8/P7/R3K3 w - - 0 1");
+        let rook_vs_nothing = evaluate("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+
+        assert!(rook_vs_bishop > 0);
+        assert!(rook_vs_bishop * 2 < with_pawn - PIECE_VALUES[Piece::Pawn as usize]);
+        assert!(rook_vs_nothing > PIECE_VALUES[Piece::Rook as usize] / 2);
+    }
+
+    #[test]
+    fn test_mate_scores_negate_safely() {
+        assert_eq!(NEG_MATE_SCORE.checked_neg(), Some(MATE_SCORE));
+        assert_eq!(MATE_SCORE.checked_neg(), Some(NEG_MATE_SCORE));
+    }
+
+    #[test]
+    fn test_center_control_bonus() {
+        let central = evaluate("r1bqkb1r/pppppppp/n6n/8/3PP3/2N2N2/PPP2PPP/R1BQKB1R w KQkq - 0 1");
+        let passive = evaluate("r1bqkb1r/pppppppp/n6n/8/P6P/N6N/1PPPPPP1/R1BQKB1R w KQkq - 0 1");
+
+        assert!(central > passive);
+        assert_eq!(evaluate(aether::constants::STARTING_POSITION), 0);
+    }
+
+    #[test]
+    fn test_pawn_cache_matches_fresh_evaluation() {
+        let evaluator = SimpleEvaluator::new();
+        let mut board = Board::from_fen("4k3/pp3p2/8/3P4/8/8/PP6/4K3 w - - 0 1").unwrap();
+
+        let first = evaluator.evaluate(&board);
+        assert_eq!(evaluator.evaluate(&board), first);
+
+        // the passed d-pawn advancing changes the pawn score
+        let mv = board.parse_san("d6").unwrap();
+        board.make_move(&mv);
+        assert_eq!(
+            evaluator.evaluate(&board),
+            SimpleEvaluator::new().evaluate(&board)
+        );
+
+        board.undo_move(&mv);
+        assert_eq!(evaluator.evaluate(&board), first);
+    }
+
+    #[test]
+    fn test_knight_outpost_bonus() {
+        // on d5 behind the e4 pawn, only a c-pawn could ever chase it away
+        let outpost = evaluate("4k3/6p1/8/3N4/4P3/8/8/4K3 w - - 0 1");
+        let contestable = evaluate("4k3/2p5/8/3N4/4P3/8/8/4K3 w - - 0 1");
+
+        assert!(outpost > contestable);
+    }
+
+    #[test]
+    fn test_bad_bishop_penalty() {
+        // the c1 bishop shares its dark squares with the blocked d4 and e3
+        // pawns, f1 does not
+        let bad = evaluate("4k3/pp6/8/3p4/3Pp3/4P3/8/2B1K3 w - - 0 1");
+        let good = evaluate("4k3/pp6/8/3p4/3Pp3/4P3/8/4KB2 w - - 0 1");
+
+        assert!(good > bad);
+    }
+
+    #[test]
+    fn test_bad_bishop_counts_only_blocked_pawns() {
+        let minor_pieces = |fen: &str| {
+            SimpleEvaluator::new()
+                .evaluate_trace(&Board::from_fen(fen).unwrap())
+                .minor_pieces
+        };
+
+        // same dark-square pawns, free to advance or stuck behind black's
+        assert_eq!(minor_pieces("4k3/8/8/8/3P4/4P3/8/2B1K3 w - - 0 1"), 0);
+        assert!(minor_pieces("4k3/8/8/3p4/3Pp3/4P3/8/2B1K3 w - - 0 1") < 0);
+    }
+
+    #[test]
+    fn test_evaluate_trace_sums_to_evaluate() {
+        let evaluator = SimpleEvaluator::new();
+        for fen in [
+            aether::constants::STARTING_POSITION,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 0 1",
+            "4k3/8/8/3b4/8/8/8/R3K3 w - - 0 1",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            let trace = evaluator.evaluate_trace(&board);
+            let sum: Score = trace.components().iter().map(|(_, score)| score).sum();
+
+            assert_eq!(sum + trace.scaling, trace.total);
+            let relative = match board.turn {
+                Color::White => trace.total,
+                Color::Black => -trace.total,
+            };
+            assert_eq!(relative, evaluator.evaluate(&board));
+            assert!(trace.to_string().contains("material"));
+        }
+    }
+}
@@ -0,0 +1,413 @@
+use aether::bitboard::Bitboard;
+use aether::board::*;
+use aether::movegen;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+    fn perft(board: &mut Board, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let mut moves = Vec::new();
+        movegen::legal(board, &mut moves);
+
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        let mut nodes = 0;
+        for mv in moves.iter() {
+            board.make_move(mv);
+            nodes += perft(board, depth - 1);
+            board.undo_move(mv);
+        }
+
+        nodes
+    }
+
+    fn perft_fen(fen: &str, depth: u32) -> u64 {
+        let mut board = Board::from_fen(fen).unwrap();
+        let fen_before = board.to_fen();
+        let nodes = perft(&mut board, depth);
+
+        assert_eq!(board.to_fen(), fen_before);
+        nodes
+    }
+
+    #[test]
+    fn test_legal_starting_position() {
+        let board = Board::init();
+        let mut moves = Vec::new();
+
+        movegen::legal(&board, &mut moves);
+
+        assert_eq!(moves.len(), 20);
+    }
+
+    #[test]
+    fn test_legal_clears_previous_moves() {
+        let board = Board::init();
+        let mut moves = Vec::new();
+
+        movegen::legal(&board, &mut moves);
+        movegen::legal(&board, &mut moves);
+
+        assert_eq!(moves.len(), 20);
+    }
+
+    #[test]
+    fn test_legal_excludes_pinned_piece_moves() {
+        let board = Board::from_fen("4k3/4r3/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+        let mut pseudo_legal = Vec::new();
+        let mut legal = Vec::new();
+
+        movegen::pseudo_legal(&board, &mut pseudo_legal);
+        movegen::legal(&board, &mut legal);
+
+        assert!(pseudo_legal.iter().any(|mv| mv.piece == Piece::Knight));
+        assert!(legal.iter().all(|mv| mv.piece == Piece::King));
+        assert_eq!(legal.len(), 4);
+    }
+
+    #[test]
+    fn test_captures() {
+        let board = Board::from_fen(KIWIPETE).unwrap();
+        let mut captures = Vec::new();
+
+        movegen::captures(&board, &mut captures);
+
+        assert_eq!(captures.len(), 8);
+        assert!(captures.iter().all(|mv| mv.capture.is_some()));
+    }
+
+    #[test]
+    fn test_perft_starting_position() {
+        let fen = aether::constants::STARTING_POSITION;
+
+        assert_eq!(perft_fen(fen, 1), 20);
+        assert_eq!(perft_fen(fen, 2), 400);
+        assert_eq!(perft_fen(fen, 3), 8902);
+    }
+
+    #[test]
+    fn test_perft_kiwipete() {
+        assert_eq!(perft_fen(KIWIPETE, 1), 48);
+        assert_eq!(perft_fen(KIWIPETE, 2), 2039);
+        assert_eq!(perft_fen(KIWIPETE, 3), 97862);
+    }
+
+    #[test]
+    fn test_perft_en_passant_and_pins() {
+        let fen = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+
+        assert_eq!(perft_fen(fen, 1), 14);
+        assert_eq!(perft_fen(fen, 2), 191);
+        assert_eq!(perft_fen(fen, 3), 2812);
+        assert_eq!(perft_fen(fen, 4), 43238);
+    }
+
+    #[test]
+    fn test_perft_promotions_and_castling() {
+        let fen = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+
+        assert_eq!(perft_fen(fen, 1), 6);
+        assert_eq!(perft_fen(fen, 2), 264);
+        assert_eq!(perft_fen(fen, 3), 9467);
+    }
+
+    #[test]
+    fn test_perft_position_five() {
+        let fen = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+
+        assert_eq!(perft_fen(fen, 1), 44);
+        assert_eq!(perft_fen(fen, 2), 1486);
+        assert_eq!(perft_fen(fen, 3), 62379);
+    }
+
+    #[test]
+    fn test_perft_chess960() {
+        let fen = "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9";
+
+        assert_eq!(perft_fen(fen, 1), 21);
+        assert_eq!(perft_fen(fen, 2), 528);
+        assert_eq!(perft_fen(fen, 3), 12189);
+    }
+
+    #[test]
+    fn test_perft_chess960_more_positions() {
+        assert_eq!(
+            perft_fen(
+                "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+                3
+            ),
+            18002
+        );
+        assert_eq!(
+            perft_fen(
+                "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+                3
+            ),
+            10471
+        );
+    }
+
+    #[test]
+    fn test_chess960_castle_onto_rook_square() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/5KR1 w G - 0 1").unwrap();
+        let mut moves = Vec::new();
+
+        movegen::legal(&board, &mut moves);
+        let castle = *moves.iter().find(|mv| mv.castling).unwrap();
+        assert_eq!(castle.to, Board::square_to_index("g1"));
+
+        board.make_move(&castle);
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/5RK1 b - - 1 1");
+
+        board.undo_move(&castle);
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/5KR1 w G - 0 1");
+    }
+
+    #[test]
+    fn test_double_check_only_king_moves() {
+        // the d5 knight and the e8 rook both give check, the white queen
+        // could otherwise capture the knight
+        let board = Board::from_fen("4r1k1/8/8/Q2n4/8/4K3/8/8 w - - 0 1").unwrap();
+        let mut moves = Vec::new();
+
+        assert!(board.checkers().more_than_one());
+        movegen::legal(&board, &mut moves);
+
+        assert!(!moves.is_empty());
+        assert!(moves.iter().all(|mv| mv.piece == Piece::King));
+        assert!(moves.iter().all(|mv| board.leaves_king_safe(mv)));
+    }
+
+    #[test]
+    fn test_queen_promotion_comes_first() {
+        let board = Board::from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let mut moves = Vec::new();
+
+        for generate in [movegen::legal, movegen::captures] {
+            generate(&board, &mut moves);
+            for to in [56, 57] {
+                let promotions: Vec<Piece> = moves
+                    .iter()
+                    .filter(|mv| mv.to == to)
+                    .filter_map(|mv| mv.promotion)
+                    .collect();
+                if !promotions.is_empty() {
+                    assert_eq!(
+                        promotions,
+                        vec![Piece::Queen, Piece::Rook, Piece::Bishop, Piece::Knight]
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_move_picker_yields_legal_moves() {
+        for fen in [
+            aether::constants::STARTING_POSITION,
+            KIWIPETE,
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+            "4k3/8/8/8/8/8/8/5KR1 w G - 0 1",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            let mut legal = Vec::new();
+            movegen::legal(&board, &mut legal);
+
+            let mut picker = movegen::MovePicker::new();
+            let mut picked = Vec::new();
+            while let Some(mv) = picker.next(&board) {
+                picked.push(mv);
+            }
+
+            assert_eq!(picked.len(), legal.len());
+            assert!(legal.iter().all(|mv| picked.contains(mv)));
+        }
+    }
+
+    #[test]
+    fn test_move_picker_stages() {
+        // Qxd5 loses the queen to the e6 pawn, Bxb7 wins a pawn for free
+        let board = Board::from_fen("4k3/1p6/4p3/3p4/8/8/6B1/3QK3 w - - 0 1").unwrap();
+        let mut picker = movegen::MovePicker::new();
+        let mut picked = Vec::new();
+        while let Some(mv) = picker.next(&board) {
+            picked.push(mv);
+        }
+
+        let first_quiet = picked.iter().position(|mv| mv.capture.is_none()).unwrap();
+        assert!(picked[..first_quiet].iter().all(|mv| board.see(mv) >= 0));
+        assert!(picked[first_quiet..]
+            .iter()
+            .all(|mv| mv.capture.is_none() || board.see(mv) < 0));
+        assert_eq!(picked.last().unwrap().piece, Piece::Queen);
+        assert!(picked.last().unwrap().capture.is_some());
+    }
+
+    #[test]
+    fn test_checks_direct_and_discovered() {
+        // the e3 knight blocks the e1 rook, the d1 queen can check from a4 or d7
+        let board = Board::from_fen("4k3/8/8/8/8/4N3/8/3QR1K1 w - - 0 1").unwrap();
+        let mut moves = Vec::new();
+        movegen::checks(&board, &mut moves);
+
+        let knight_moves = moves.iter().filter(|mv| mv.piece == Piece::Knight).count();
+        assert_eq!(knight_moves, 7);
+        assert!(moves
+            .iter()
+            .any(|mv| mv.piece == Piece::Queen && mv.to == Board::square_to_index("a4")));
+        assert!(moves
+            .iter()
+            .any(|mv| mv.piece == Piece::Queen && mv.to == Board::square_to_index("d7")));
+        assert!(!moves.iter().any(|mv| mv.to == Board::square_to_index("d2")));
+
+        for mv in moves.iter() {
+            let mut after = board.clone();
+            after.make_move(mv);
+            assert!(after.is_in_check(Color::Black));
+            assert!(mv.capture.is_none());
+        }
+    }
+
+    #[test]
+    fn test_checks_match_make_move() {
+        let board = Board::from_fen(KIWIPETE).unwrap();
+        let mut legal = Vec::new();
+        let mut checks = Vec::new();
+        movegen::legal(&board, &mut legal);
+        movegen::checks(&board, &mut checks);
+
+        for mv in legal.iter().filter(|mv| mv.capture.is_none()) {
+            let mut after = board.clone();
+            after.make_move(mv);
+            assert_eq!(checks.contains(mv), after.is_in_check(after.turn));
+        }
+    }
+
+    #[test]
+    fn test_legal_to_target_mask() {
+        let board = Board::from_fen(KIWIPETE).unwrap();
+        let mut legal = Vec::new();
+        movegen::legal(&board, &mut legal);
+
+        let mut union = Vec::new();
+        let mut moves = Vec::new();
+        for square in 0..64 {
+            movegen::legal_to(&board, Bitboard::from_index(square), &mut moves);
+            assert!(moves.iter().all(|mv| mv.to == square));
+            union.extend(moves.iter().copied());
+        }
+
+        assert_eq!(union.len(), legal.len());
+        assert!(legal.iter().all(|mv| union.contains(mv)));
+
+        movegen::legal_to(&board, Bitboard::new(), &mut moves);
+        assert!(moves.is_empty());
+    }
+
+    #[test]
+    fn test_legal_to_matches_filtered_generation() {
+        let masks = [
+            aether::constants::DARK_SQUARES,
+            Bitboard(0x00FF_0000_0000_FF00),
+            Bitboard(0xFF00_0000_0000_00FF),
+            Bitboard::from_index(43),
+        ];
+        for fen in [
+            KIWIPETE,
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+            "4k3/8/8/8/8/8/3r4/R3K2R w KQ - 0 1",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            let mut legal = Vec::new();
+            let mut moves = Vec::new();
+            movegen::legal(&board, &mut legal);
+
+            for target in masks {
+                movegen::legal_to(&board, target, &mut moves);
+                let filtered: Vec<_> = legal
+                    .iter()
+                    .copied()
+                    .filter(|mv| target.is_set(mv.to))
+                    .collect();
+                assert_eq!(moves, filtered, "{}", fen);
+            }
+        }
+    }
+
+    #[test]
+    fn test_move_list() {
+        // the position with the most legal moves known, 218
+        let board =
+            Board::from_fen("R6R/3Q4/1Q4Q1/4Q3/2Q4Q/Q4Q2/pp1Q4/kBNN1KB1 w - - 0 1").unwrap();
+        let mut legal = Vec::new();
+        movegen::legal(&board, &mut legal);
+
+        let mut list = movegen::MoveList::new();
+        list.extend(legal.iter().copied());
+        assert_eq!(legal.len(), 218);
+        assert_eq!(&list[..], &legal[..]);
+
+        list.sort_by_key(|mv| mv.to);
+        assert!(list.windows(2).all(|pair| pair[0].to <= pair[1].to));
+
+        list.clear();
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_lone_king_moves() {
+        let board = Board::from_fen("8/8/8/3k4/8/8/1Q6/4K3 b - - 0 1").unwrap();
+        let mut moves = Vec::new();
+        movegen::legal(&board, &mut moves);
+
+        assert!(board.has_only_king(Color::Black));
+        assert!(!board.has_only_king(Color::White));
+        assert!(moves.iter().all(|mv| mv.piece == Piece::King));
+
+        // d4 and e5 are covered by the queen on b2
+        let mut targets: Vec<usize> = moves.iter().map(|mv| mv.to).collect();
+        targets.sort();
+        let mut expected: Vec<usize> = ["c4", "d4", "e4", "c5", "e5", "c6", "d6", "e6"]
+            .iter()
+            .map(|square| Board::square_to_index(square))
+            .collect();
+        expected.retain(|&square| !board.is_square_attacked(square, Color::White));
+        expected.sort();
+        assert_eq!(targets, expected);
+    }
+
+    #[test]
+    fn test_checks_only_revealing_quiet_moves() {
+        // every quiet bishop move uncovers the e1 rook, Bxg5 checks too but
+        // is a capture, and no king move gives check
+        let board = Board::from_fen("4k3/8/8/6p1/8/4B3/8/4RK2 w - - 0 1").unwrap();
+        let mut checks = Vec::new();
+        let mut legal = Vec::new();
+        movegen::checks(&board, &mut checks);
+        movegen::legal(&board, &mut legal);
+
+        let mut bishop_quiets: Vec<Move> = legal
+            .iter()
+            .filter(|mv| mv.piece == Piece::Bishop && mv.capture.is_none())
+            .copied()
+            .collect();
+        bishop_quiets.sort_by_key(|mv| mv.to);
+        checks.sort_by_key(|mv| mv.to);
+
+        assert_eq!(checks, bishop_quiets);
+        assert!(legal
+            .iter()
+            .any(|mv| mv.capture.is_some() && board.gives_check(mv)));
+    }
+}
@@ -0,0 +1,152 @@
+use aether::board::*;
+use aether::perft::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+    #[test]
+    fn test_perft_state_from_fen() {
+        let mut state = PerftState::from_fen(KIWIPETE).unwrap();
+
+        assert_eq!(state.perft(1), 48);
+        assert_eq!(state.perft(2), 2039);
+        assert_eq!(state.board.to_fen(), KIWIPETE);
+    }
+
+    #[test]
+    fn test_perft_state_from_board() {
+        let mut state = PerftState::from_board(&Board::init());
+
+        assert_eq!(state.perft(0), 1);
+        assert_eq!(state.perft(3), 8902);
+    }
+
+    #[test]
+    fn test_perft_state_from_invalid_fen() {
+        assert!(matches!(
+            PerftState::from_fen("not a fen"),
+            Err(FenError::MissingFields(3))
+        ));
+    }
+
+    #[test]
+    fn test_perft_hashed_matches_perft() {
+        for fen in [aether::constants::STARTING_POSITION, KIWIPETE] {
+            let mut state = PerftState::from_fen(fen).unwrap();
+            for depth in 1..=3 {
+                assert_eq!(state.perft_hashed(depth), state.perft(depth));
+            }
+            assert_eq!(state.board.to_fen(), fen);
+        }
+
+        let mut state = PerftState::from_board(&Board::init());
+        assert_eq!(state.perft_hashed(4), 197281);
+    }
+
+    // slow in debug builds, run with --release -- --ignored
+    #[test]
+    #[ignore]
+    fn test_perft_hashed_matches_perft_deep() {
+        for fen in [aether::constants::STARTING_POSITION, KIWIPETE] {
+            let mut state = PerftState::from_fen(fen).unwrap();
+            for depth in 4..=5 {
+                assert_eq!(state.perft_hashed(depth), state.perft(depth));
+            }
+        }
+    }
+
+    #[test]
+    fn test_perft_stats_kiwipete() {
+        let mut state = PerftState::from_fen(KIWIPETE).unwrap();
+        let expected = [
+            (48, 8, 0, 2, 0, 0, 0),
+            (2039, 351, 1, 91, 0, 3, 0),
+            (97862, 17102, 45, 3162, 0, 993, 1),
+        ];
+
+        for (depth, (nodes, captures, en_passant, castles, promotions, checks, checkmates)) in
+            (1..).zip(expected)
+        {
+            assert_eq!(
+                state.perft_stats(depth),
+                PerftStats {
+                    nodes,
+                    captures,
+                    en_passant,
+                    castles,
+                    promotions,
+                    checks,
+                    checkmates,
+                }
+            );
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn test_perft_stats_kiwipete_deep() {
+        let mut state = PerftState::from_fen(KIWIPETE).unwrap();
+        let expected = [
+            (4, 4085603, 757163, 1929, 128013, 15172, 25523, 43),
+            (5, 193690690, 35043416, 73365, 4993637, 8392, 3309887, 30171),
+        ];
+
+        for (depth, nodes, captures, en_passant, castles, promotions, checks, checkmates) in
+            expected
+        {
+            assert_eq!(
+                state.perft_stats(depth),
+                PerftStats {
+                    nodes,
+                    captures,
+                    en_passant,
+                    castles,
+                    promotions,
+                    checks,
+                    checkmates,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_perft_stats_promotions() {
+        // position 4 of the standard perft suite
+        let mut state = PerftState::from_fen(
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        )
+        .unwrap();
+        let stats = state.perft_stats(2);
+
+        assert_eq!(stats.nodes, 264);
+        assert_eq!(stats.captures, 87);
+        assert_eq!(stats.castles, 6);
+        assert_eq!(stats.promotions, 48);
+        assert_eq!(stats.checks, 10);
+    }
+
+    #[test]
+    fn test_perft_parallel_matches_perft() {
+        let mut state = PerftState::from_fen(KIWIPETE).unwrap();
+        let serial = state.perft(3);
+
+        for threads in [1, 3, 8] {
+            assert_eq!(state.perft_parallel(3, threads), serial);
+        }
+        assert_eq!(
+            PerftState::from_board(&Board::init()).perft_parallel(4, 4),
+            197281
+        );
+    }
+    #[test]
+    #[ignore]
+    fn test_perft_parallel_matches_perft_depth_6() {
+        let mut state = PerftState::from_board(&Board::init());
+
+        assert_eq!(state.perft_parallel(6, 8), 119060324);
+        assert_eq!(state.perft(6), 119060324);
+    }
+}
@@ -0,0 +1,178 @@
+use aether::board::*;
+use aether::pgn::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(game: &mut Game, board: &mut Board, sans: &[&str]) {
+        for san in sans {
+            let mv = board.parse_san(san).unwrap();
+            board.make_move(&mv);
+            game.push(mv);
+        }
+    }
+
+    #[test]
+    fn test_game_result_from_outcome() {
+        let mated = Board::from_fen("3R2k1/5ppp/8/8/8/8/8/6K1 b - - 0 1").unwrap();
+        let outcome = mated.game_result().unwrap();
+
+        assert_eq!(GameResult::from(outcome), GameResult::WhiteWins);
+        assert_eq!(
+            GameResult::from(GameOutcome::Draw(DrawReason::FiftyMoves)),
+            GameResult::Draw
+        );
+    }
+
+    #[test]
+    fn test_to_pgn_starting_position() {
+        let mut game = Game::new();
+        let mut board = Board::init();
+        play(&mut game, &mut board, &["e4", "e5", "Nf3", "Nc6", "Bb5"]);
+        game.result = GameResult::Draw;
+
+        let pgn = game.to_pgn(&[
+            ("White".to_string(), "Alice".to_string()),
+            ("Opening".to_string(), "Ruy Lopez".to_string()),
+        ]);
+
+        assert_eq!(
+            pgn,
+            "[Event \"?\"]\n\
+             [Site \"?\"]\n\
+             [Date \"????.??.??\"]\n\
+             [Round \"?\"]\n\
+             [White \"Alice\"]\n\
+             [Black \"?\"]\n\
+             [Result \"1/2-1/2\"]\n\
+             [Opening \"Ruy Lopez\"]\n\
+             \n\
+             1. e4 e5 2. Nf3 Nc6 3. Bb5 1/2-1/2\n"
+        );
+    }
+
+    #[test]
+    fn test_to_pgn_from_fen_with_black_to_move() {
+        let fen = "4k3/8/8/8/8/8/8/R3K3 b Q - 0 30";
+        let mut game = Game::from_fen(fen).unwrap();
+        let mut board = Board::from_fen(fen).unwrap();
+        play(&mut game, &mut board, &["Kd7", "O-O-O+"]);
+
+        let pgn = game.to_pgn(&[]);
+
+        assert!(pgn.contains("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/8/R3K3 b Q - 0 30\"]\n"));
+        assert!(pgn.ends_with("\n30... Kd7 31. O-O-O+ *\n"));
+    }
+
+    #[test]
+    fn test_from_board_replays_history() {
+        let mut board = Board::init();
+        for san in ["d4", "d5", "c4"] {
+            let mv = board.parse_san(san).unwrap();
+            board.make_move(&mv);
+        }
+
+        let game = Game::from_board(&board);
+
+        assert_eq!(game.start.to_fen(), Board::init().to_fen());
+        assert!(!game.to_pgn(&[]).contains("FEN"));
+        assert!(game.to_pgn(&[]).ends_with("1. d4 d5 2. c4 *\n"));
+    }
+
+    #[test]
+    fn test_to_pgn_wraps_long_movetext() {
+        let mut game = Game::new();
+        let mut board = Board::init();
+        let shuffle = ["Nf3", "Nf6", "Ng1", "Ng8"];
+        for _ in 0..10 {
+            play(&mut game, &mut board, &shuffle);
+        }
+
+        let pgn = game.to_pgn(&[]);
+        let movetext = pgn.split("\n\n").nth(1).unwrap();
+
+        assert!(movetext.lines().count() > 1);
+        assert!(movetext.lines().all(|line| line.len() <= 80));
+    }
+
+    #[test]
+    fn test_from_pgn_strips_comments_variations_and_nags() {
+        let pgn = "[Event \"Casual \\\"blitz\\\"\"]\n\
+                   [White \"Alice\"]\n\
+                   \n\
+                   1. e4 {best by test} e5 $1 2. Nf3 (2. f4 exf4 (2... d5)) 2... Nc6 ; comment\n\
+                   3.Bb5 a6!? 1-0\n";
+
+        let (game, tags) = Game::from_pgn(pgn).unwrap();
+
+        assert_eq!(
+            tags,
+            vec![
+                ("Event".to_string(), "Casual \"blitz\"".to_string()),
+                ("White".to_string(), "Alice".to_string()),
+            ]
+        );
+        assert_eq!(game.moves.len(), 6);
+        assert_eq!(game.result, GameResult::WhiteWins);
+        assert!(game
+            .to_pgn(&tags)
+            .ends_with("1. e4 e5 2. Nf3 Nc6 3. Bb5 a6 1-0\n"));
+    }
+
+    #[test]
+    fn test_from_pgn_with_fen_tag() {
+        let pgn = "[SetUp \"1\"]\n\
+                   [FEN \"4k3/8/8/8/8/8/8/R3K3 b Q - 0 30\"]\n\
+                   \n\
+                   30... Kd7 31. O-O-O+ *";
+
+        let (game, _) = Game::from_pgn(pgn).unwrap();
+
+        assert_eq!(game.start.to_fen(), "4k3/8/8/8/8/8/8/R3K3 b Q - 0 30");
+        assert_eq!(game.moves.len(), 2);
+        assert!(game.moves[1].castling);
+        assert_eq!(game.result, GameResult::Ongoing);
+    }
+
+    #[test]
+    fn test_from_pgn_round_trip() {
+        let mut game = Game::new();
+        let mut board = Board::init();
+        play(
+            &mut game,
+            &mut board,
+            &["d4", "Nf6", "c4", "e6", "Nc3", "Bb4"],
+        );
+        game.result = GameResult::BlackWins;
+
+        let (parsed, _) = Game::from_pgn(&game.to_pgn(&[])).unwrap();
+
+        assert_eq!(parsed.moves, game.moves);
+        assert_eq!(parsed.result, GameResult::BlackWins);
+    }
+
+    #[test]
+    fn test_from_pgn_errors() {
+        assert_eq!(
+            Game::from_pgn("1. e4 e5 2. Ke3").err(),
+            Some(PgnError::InvalidMove {
+                token: "Ke3".to_string(),
+                ply: 3,
+                error: MoveError::IllegalMove("Ke3".to_string()),
+            })
+        );
+        assert_eq!(
+            Game::from_pgn("1. e4 {unterminated").err(),
+            Some(PgnError::UnterminatedComment)
+        );
+        assert!(matches!(
+            Game::from_pgn("[FEN \"not a fen\"]\n1. e4"),
+            Err(PgnError::InvalidFen(_))
+        ));
+        assert!(matches!(
+            Game::from_pgn("[Event]\n1. e4"),
+            Err(PgnError::InvalidTag(_))
+        ));
+    }
+}